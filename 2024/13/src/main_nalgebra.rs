@@ -52,13 +52,13 @@ fn parse_system(a: &str, b: &str, prize: &str) -> (Vector2<f64>, Vector2<f64>, V
 fn solve_machine(
     (button_a, button_b, prize): (Vector2<f64>, Vector2<f64>, Vector2<f64>),
 ) -> Option<(usize, usize)> {
-    let a = Matrix2::from_rows(&[button_a.transpose(), button_b.transpose()]);
+    let a = Matrix2::from_columns(&[button_a, button_b]);
     let b = prize;
-    println!("Finding solution for {a}[x y] = {b}");
     if let Some(solution) = a.lu().solve(&b) {
-        println!("Solution: x = {}, y = {}", solution[0], solution[1]);
-        if solution[0].fract().abs() <= 4f64 * f64::EPSILON
-            && solution[1].fract().abs() <= 4f64 * f64::EPSILON
+        if (solution[0] - solution[0].round()).abs() <= 1e-3
+            && (solution[1] - solution[1].round()).abs() <= 1e-3
+            && solution[0].round() >= 0f64
+            && solution[1].round() >= 0f64
         {
             return Some((solution[0].round() as usize, solution[1].round() as usize));
         }
@@ -66,7 +66,7 @@ fn solve_machine(
     None
 }
 
-fn part1(input: Vec<String>) -> anyhow::Result<usize> {
+pub(crate) fn part1(input: Vec<String>) -> anyhow::Result<usize> {
     let mut total = 0;
     for machine in input.chunks(4) {
         let system = parse_system(&machine[0], &machine[1], &machine[2]);
@@ -82,8 +82,9 @@ fn part1(input: Vec<String>) -> anyhow::Result<usize> {
 fn part2(input: Vec<String>) -> anyhow::Result<usize> {
     let mut total = 0;
     for machine in input.chunks(4) {
-        let system = parse_system(&machine[0], &machine[1], &machine[2]);
-        if let Some(presses) = solve_machine(system) {
+        let (a, b, prize) = parse_system(&machine[0], &machine[1], &machine[2]);
+        let prize = prize.add_scalar(10000000000000f64);
+        if let Some(presses) = solve_machine((a, b, prize)) {
             total += 3 * presses.0 + presses.1;
         }
     }
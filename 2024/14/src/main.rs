@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 
-use common::grid::coordinates::Point;
+use common::{
+    grid::{coordinates::Point, Grid},
+    viz,
+};
 use itertools::Itertools;
 
 fn main() -> anyhow::Result<()> {
@@ -37,19 +40,22 @@ fn part2(input: Vec<String>) -> anyhow::Result<i64> {
     let width = 101;
     let height = 103;
 
+    // The tree is found by eye, so there's nothing to do without frames to look at.
+    if !viz::enabled() {
+        return Ok(0);
+    }
     for i in 1..(101 * 103) {
         // for i in 1..100000000000 {
         let resulting_positions = simulate_robots_set(i, height, width, &bots);
-        for y in 0..height {
-            for x in 0..width {
-                if resulting_positions.contains(&(x, y)) {
-                    print!("*");
-                } else {
-                    print!(" ");
-                }
-            }
-            println!();
+        // The robots arranged into a picture don't overlap, so only those seconds are worth a look.
+        if resulting_positions.len() < bots.len() {
+            continue;
+        }
+        let mut floor = Grid::from_parts(vec![false; width * height], width, height);
+        for &position in &resulting_positions {
+            floor.set(position, true);
         }
+        viz::record(&floor, |_, &robot| if robot { '*' } else { ' ' });
     }
     Ok(0)
 }
@@ -84,7 +90,7 @@ impl Robot {
 }
 
 fn parse_input(input: Vec<String>) -> Vec<Robot> {
-    input.iter().map(|line| Robot::from_str(line)).collect()
+    common::input::parse_lines(input, Robot::from_str)
 }
 
 // x starts at 2
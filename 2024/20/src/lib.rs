@@ -0,0 +1,115 @@
+use common::{
+    grid::{coordinates::Point, Grid},
+    Solution,
+};
+use pathfinding::directed::bfs::bfs;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: i32 = 2024;
+    const DAY: u8 = 20;
+
+    type Parsed = RaceTrack;
+
+    fn parse(input: &str) -> anyhow::Result<RaceTrack> {
+        parse(input)
+    }
+
+    fn part1(track: &RaceTrack) -> anyhow::Result<impl ToString> {
+        part1(track)
+    }
+
+    fn part2(track: &RaceTrack) -> anyhow::Result<impl ToString> {
+        part2(track)
+    }
+}
+
+/// The only path along the course from start to end, which both parts look for cheats along.
+pub struct RaceTrack {
+    shortest_path: Vec<Point>,
+}
+
+fn parse(input: &str) -> anyhow::Result<RaceTrack> {
+    let (course, locations) =
+        Grid::from_input(input.lines().map(String::from).collect(), &['S', 'E']);
+    // print!("{course}");
+    let start = locations.get(&'S').unwrap()[0];
+    let end = locations.get(&'E').unwrap()[0];
+    // let starting_state = RaceState {
+    //     point: start,
+    //     remaining_cheats: 2,
+    // };
+    let shortest_path = bfs(
+        &start,
+        |point| {
+            course
+                .neighbours_where(*point, |_, &c| c != '#')
+                .map(|(neighbour, _)| neighbour)
+                .collect::<Vec<Point>>()
+        },
+        |point| *point == end,
+    )
+    .unwrap();
+    Ok(RaceTrack { shortest_path })
+}
+
+fn part1(track: &RaceTrack) -> anyhow::Result<i64> {
+    let shortest_path = &track.shortest_path;
+    let mut shortcuts = 0;
+    for i in 0..shortest_path.len() {
+        for j in i + 102..shortest_path.len() {
+            if shortest_path[i].dist(shortest_path[j]) == 2 {
+                if shortest_path[i].0 == shortest_path[j].0
+                    || shortest_path[i].1 == shortest_path[j].1
+                {
+                    shortcuts += 1;
+                } else {
+                    shortcuts += 2;
+                }
+            }
+        }
+    }
+
+    Ok(shortcuts)
+}
+
+fn part2(track: &RaceTrack) -> anyhow::Result<i64> {
+    let shortest_path = &track.shortest_path;
+    let mut shortcuts = 0;
+    for i in 0..shortest_path.len() {
+        for j in i + 102..shortest_path.len() {
+            let dist = shortest_path[i].dist(shortest_path[j]);
+            if dist <= 20 && (j - i) - dist >= 100 {
+                shortcuts += 1;
+            }
+        }
+    }
+
+    Ok(shortcuts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let inputs = [include_str!("../testcase_1.txt")];
+        let outputs = [44];
+        assert_eq!(inputs.len(), outputs.len());
+        for (input, &output) in inputs.into_iter().zip(outputs.iter()) {
+            assert_eq!(part1(&parse(input).unwrap()).unwrap(), output);
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        let inputs = [include_str!("../testcase_1.txt")];
+        let outputs = [285];
+        assert_eq!(inputs.len(), outputs.len());
+        for (input, &output) in inputs.into_iter().zip(outputs.iter()) {
+            assert_eq!(part1(&parse(input).unwrap()).unwrap(), output);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+fn main() -> anyhow::Result<()> {
+    common::Registry::new()
+        .register::<advent_2024_12::Day12>()
+        .register::<advent_2024_13::Day13>()
+        .register::<advent_2024_15::Day15>()
+        .register::<advent_2024_16::Day16>()
+        .register::<advent_2024_17::Day17>()
+        .register::<advent_2024_18::Day18>()
+        .register::<advent_2024_19::Day19>()
+        .register::<advent_2024_20::Day20>()
+        .register::<advent_2024_22::Day22>()
+        .main()
+}
@@ -1,22 +1,43 @@
+use std::collections::HashMap;
+
 use anyhow::{bail, Result};
 use regex::Regex;
 use reqwest::blocking;
 use scraper::{Html, Selector};
 
-// This email is here so the advent of code developer can contact me if this framework sends a
-// bunch of potentially service disrupting requests. If you change anything in the advent crate,
-// please put your email here, and pay attention to your email. Don't ruin adventofcode for others
-// and save the effort of messing with this for solving the puzzles in the advent :)
-const CONTACT_EMAIL: &str = "taqtb6p09@mozmail.com";
+mod http_cache;
+
+pub use http_cache::HttpCache;
+
+/// Who requests are made as: the session to use, and an email address the advent of code developer
+/// can use to contact you if this framework sends a bunch of potentially service disrupting
+/// requests. Don't ruin adventofcode for others :)
+#[derive(Debug, Clone)]
+pub struct Identity {
+    pub session_cookie: String,
+    pub contact_email: String,
+}
+
+impl Identity {
+    fn get(&self, client: &blocking::Client, url: &str) -> blocking::RequestBuilder {
+        client
+            .get(url)
+            .header("Cookie", format!("session={}", self.session_cookie))
+            .header("User-Agent", &self.contact_email)
+    }
 
-pub fn get_input(day: u8, year: i32, session_cookie: &str) -> Result<String> {
+    fn post(&self, client: &blocking::Client, url: &str) -> blocking::RequestBuilder {
+        client
+            .post(url)
+            .header("Cookie", format!("session={}", self.session_cookie))
+            .header("User-Agent", &self.contact_email)
+    }
+}
+
+pub fn get_input(day: u8, year: i32, identity: &Identity) -> Result<String> {
     let client = blocking::Client::new();
     let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-    let response = client
-        .get(&url)
-        .header("Cookie", format!("session={session_cookie}"))
-        .header("User-Agent", CONTACT_EMAIL) // This is so if
-        .send()?;
+    let response = identity.get(&client, &url).send()?;
     if response.status().is_success() {
         response.text().map_err(anyhow::Error::from)
     } else {
@@ -24,6 +45,46 @@ pub fn get_input(day: u8, year: i32, session_cookie: &str) -> Result<String> {
     }
 }
 
+pub fn get_puzzle_page(
+    day: u8,
+    year: i32,
+    identity: &Identity,
+    cache: &HttpCache,
+) -> Result<String> {
+    let client = blocking::Client::new();
+    let url = format!("https://adventofcode.com/{year}/day/{day}");
+    cache.fetch(identity.get(&client, &url))
+}
+
+pub fn get_calendar_page(year: i32, identity: &Identity, cache: &HttpCache) -> Result<String> {
+    let client = blocking::Client::new();
+    let url = format!("https://adventofcode.com/{year}");
+    cache.fetch(identity.get(&client, &url))
+}
+
+/// Reads how many stars have been earned for each day from the year's calendar page.
+pub fn get_stars(year: i32, identity: &Identity, cache: &HttpCache) -> Result<HashMap<u8, u8>> {
+    let doc = Html::parse_document(&get_calendar_page(year, identity, cache)?);
+    let selector = Selector::parse("main a").unwrap();
+    Ok(doc
+        .select(&selector)
+        .filter_map(|day_link| {
+            let classes = day_link.value().classes().collect::<Vec<_>>();
+            let day = classes
+                .iter()
+                .find_map(|class| class.strip_prefix("calendar-day")?.parse::<u8>().ok())?;
+            let stars = if classes.contains(&"calendar-verycomplete") {
+                2
+            } else if classes.contains(&"calendar-complete") {
+                1
+            } else {
+                0
+            };
+            Some((day, stars))
+        })
+        .collect())
+}
+
 #[derive(Debug)]
 pub enum AdventResult {
     Correct,
@@ -37,18 +98,13 @@ pub fn submit_answer(
     year: i32,
     level: u8,
     answer: &str,
-    session_cookie: &str,
+    identity: &Identity,
 ) -> Result<AdventResult> {
     let client = blocking::Client::new();
     let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
     let level = level.to_string();
     let params = [("level", &level[..]), ("answer", answer)];
-    let response = client
-        .post(&url)
-        .header("Cookie", format!("session={session_cookie}"))
-        .header("User-Agent", CONTACT_EMAIL)
-        .form(&params)
-        .send()?;
+    let response = identity.post(&client, &url).form(&params).send()?;
     if response.status().is_success() {
         let doc = Html::parse_document(&response.text()?);
         let selector = Selector::parse("body main article p").unwrap();
@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use reqwest::{
+    blocking,
+    header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    StatusCode, Url,
+};
+use tracing::debug;
+
+/// Stores response bodies on disk alongside their `ETag`/`Last-Modified` validators so pages that
+/// rarely change (puzzle descriptions, calendars) are only downloaded again when the server says
+/// they changed.
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    dir: PathBuf,
+    refresh: bool,
+}
+
+impl HttpCache {
+    /// `refresh` skips the conditional headers so the server always sends a fresh copy, which is
+    /// then stored in place of the cached one.
+    pub fn new(dir: PathBuf, refresh: bool) -> Self {
+        Self { dir, refresh }
+    }
+
+    pub fn fetch(&self, request: blocking::RequestBuilder) -> Result<String> {
+        let (client, request) = request.build_split();
+        let mut request = request?;
+        let key = cache_key(request.url());
+        let body_path = self.dir.join(format!("{key}.body"));
+        let validators_path = self.dir.join(format!("{key}.headers"));
+
+        if !self.refresh && body_path.exists() {
+            if let Ok(validators) = std::fs::read_to_string(&validators_path) {
+                for (name, value) in validators.lines().filter_map(|line| line.split_once(": ")) {
+                    let conditional_header = match name {
+                        "etag" => IF_NONE_MATCH,
+                        "last-modified" => IF_MODIFIED_SINCE,
+                        _ => continue,
+                    };
+                    request
+                        .headers_mut()
+                        .insert(conditional_header, HeaderValue::from_str(value)?);
+                }
+            }
+        }
+
+        let url = request.url().clone();
+        let response = client.execute(request)?;
+        match response.status() {
+            StatusCode::NOT_MODIFIED => {
+                debug!("{url} not modified, using cached copy");
+                Ok(std::fs::read_to_string(body_path)?)
+            }
+            status if status.is_success() => {
+                let validators = validators(response.headers());
+                let body = response.text()?;
+                std::fs::create_dir_all(&self.dir)?;
+                std::fs::write(&body_path, &body)?;
+                std::fs::write(&validators_path, validators)?;
+                Ok(body)
+            }
+            status => bail!("Failed to retrieve {url}: {status}"),
+        }
+    }
+}
+
+fn cache_key(url: &Url) -> String {
+    let path = url.path().trim_matches('/');
+    if path.is_empty() {
+        String::from("index")
+    } else {
+        path.replace('/', "_")
+    }
+}
+
+fn validators(headers: &HeaderMap) -> String {
+    [("etag", ETAG), ("last-modified", LAST_MODIFIED)]
+        .into_iter()
+        .filter_map(|(name, header)| {
+            let value = headers.get(header)?.to_str().ok()?;
+            Some(format!("{name}: {value}\n"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    /// Stand-in for adventofcode.com that serves `body` with an `ETag` and answers `304 Not
+    /// Modified` when the client already has it. Every request's headers are sent back over the
+    /// returned channel.
+    fn serve(body: &'static str, requests: usize) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2024/day/17", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut head = String::new();
+                let mut reader = BufReader::new(&stream);
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head.push_str(&line.to_lowercase());
+                }
+                let response = if head.contains("if-none-match: \"v1\"") {
+                    String::from("HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n")
+                } else {
+                    format!(
+                        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nLast-Modified: Tue, 17 Dec 2024 05:00:00 GMT\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+                sender.send(head).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    #[test]
+    fn it_revalidates_cached_pages() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let (url, requests) = serve("<main>puzzle</main>", 2);
+        let cache = HttpCache::new(cache_dir.path().to_path_buf(), false);
+        let client = blocking::Client::new();
+
+        assert_eq!(
+            cache.fetch(client.get(&url)).unwrap(),
+            "<main>puzzle</main>"
+        );
+        let first = requests.recv().unwrap();
+        assert!(!first.contains("if-none-match"));
+        assert!(!first.contains("if-modified-since"));
+
+        assert_eq!(
+            cache.fetch(client.get(&url)).unwrap(),
+            "<main>puzzle</main>"
+        );
+        let second = requests.recv().unwrap();
+        assert!(second.contains("if-none-match: \"v1\""));
+        assert!(second.contains("if-modified-since: tue, 17 dec 2024 05:00:00 gmt"));
+    }
+
+    #[test]
+    fn it_skips_validation_on_refresh() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let (url, requests) = serve("<main>part two</main>", 2);
+        let client = blocking::Client::new();
+
+        let cache = HttpCache::new(cache_dir.path().to_path_buf(), false);
+        cache.fetch(client.get(&url)).unwrap();
+        requests.recv().unwrap();
+
+        let cache = HttpCache::new(cache_dir.path().to_path_buf(), true);
+        assert_eq!(
+            cache.fetch(client.get(&url)).unwrap(),
+            "<main>part two</main>"
+        );
+        assert!(!requests.recv().unwrap().contains("if-none-match"));
+        assert!(cache_dir.path().join("2024_day_17.body").exists());
+    }
+}
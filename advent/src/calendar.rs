@@ -0,0 +1,90 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+/// The first year the advent ran.
+pub const FIRST_YEAR: i32 = 2015;
+
+/// How many puzzles a year's advent has. From 2025 on the event runs for twelve days instead of
+/// twenty-five.
+pub fn days_in_year(year: i32) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+pub fn days(year: i32) -> RangeInclusive<u8> {
+    1..=days_in_year(year)
+}
+
+/// Puzzles unlock at midnight US Eastern time, which the site treats as a fixed UTC-5.
+pub fn unlock_time(year: i32, day: u8) -> DateTime<Utc> {
+    FixedOffset::west_opt(5 * 60 * 60)
+        .unwrap()
+        .with_ymd_and_hms(year, 12, day.into(), 0, 0, 0)
+        .unwrap()
+        .to_utc()
+}
+
+/// The last day of each advent only has one puzzle; its second star is awarded for having all the
+/// others.
+pub fn has_part_two(year: i32, day: u8) -> bool {
+    day != days_in_year(year)
+}
+
+/// Years whose first puzzle has unlocked by `now`, most recent first.
+pub fn available_years(now: DateTime<Utc>) -> Vec<i32> {
+    (FIRST_YEAR..=now.year())
+        .rev()
+        .filter(|&year| unlock_time(year, 1) <= now)
+        .collect()
+}
+
+/// Checks that `day` exists in `year`'s advent and, if `part` is given, that the day has that part.
+pub fn validate(year: i32, day: u8, part: Option<u8>) -> Result<()> {
+    if year < FIRST_YEAR {
+        bail!("The advent started in {FIRST_YEAR}, there is no {year} advent");
+    }
+    if !days(year).contains(&day) {
+        bail!(
+            "The {year} advent only has days 1 to {}, there is no day {day}",
+            days_in_year(year)
+        );
+    }
+    match part {
+        Some(1) | None => Ok(()),
+        Some(2) if has_part_two(year, day) => Ok(()),
+        Some(2) => bail!("Day {day} of {year} only has one part"),
+        Some(part) => bail!("Puzzles only have parts 1 and 2, there is no part {part}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_shortens_the_advent_from_2025() {
+        assert_eq!(days(2024), 1..=25);
+        assert_eq!(days(2025), 1..=12);
+        assert!(!has_part_two(2024, 25));
+        assert!(!has_part_two(2025, 12));
+        assert!(validate(2025, 13, None).is_err());
+        assert!(validate(2025, 12, Some(2)).is_err());
+        assert!(validate(2024, 12, Some(2)).is_ok());
+    }
+
+    #[test]
+    fn it_unlocks_at_midnight_eastern() {
+        let unlock = unlock_time(2024, 1);
+        assert_eq!(unlock.to_rfc3339(), "2024-12-01T05:00:00+00:00");
+        assert_eq!(available_years(unlock)[0], 2024);
+        assert_eq!(
+            available_years(unlock - chrono::Duration::seconds(1))[0],
+            2023
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+const CONFIG_FILE: &str = "advent.toml";
+
+/// Workspace settings read from `advent.toml` in the workspace root. A missing file is the same as
+/// an empty one, but `contact_email` has to be set before anything is sent to adventofcode.com.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Sent as the User-Agent so the advent of code developer can contact you if this framework
+    /// sends a bunch of potentially service disrupting requests. Every fork has to set its own.
+    pub contact_email: Option<String>,
+
+    /// Year to use when it isn't given as an argument or implied by the current directory.
+    pub year: Option<i32>,
+
+    /// Day to use when it isn't given as an argument or implied by the current directory.
+    pub day: Option<u8>,
+
+    /// Part to run or submit when `--part` isn't given.
+    pub part: u8,
+
+    /// Where inputs, downloaded pages and the session cookie are kept, relative to the workspace.
+    pub cache_dir: PathBuf,
+
+    /// Cargo profile solutions are built and run with.
+    pub profile: String,
+
+    /// `main.rs` for new days, relative to the workspace. The built-in template is used if unset.
+    pub template: Option<PathBuf>,
+
+    /// Crates added to every new day alongside `common`.
+    pub crates: Vec<String>,
+
+    pub git: GitConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitConfig {
+    /// Commit the day's crate whenever the site accepts an answer.
+    pub commit_on_success: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            contact_email: None,
+            year: None,
+            day: None,
+            part: 1,
+            cache_dir: PathBuf::from(".cache"),
+            profile: String::from("dev"),
+            template: None,
+            crates: vec![String::from("anyhow"), String::from("tracing")],
+            git: GitConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load(ws_root: &Path) -> Result<Self> {
+        let path = ws_root.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let config: Self = toml::from_str(&std::fs::read_to_string(&path)?)
+            .with_context(|| format!("Invalid {}", path.display()))?;
+        config
+            .validate(ws_root)
+            .with_context(|| format!("Invalid {}", path.display()))?;
+        Ok(config)
+    }
+
+    fn validate(&self, ws_root: &Path) -> Result<()> {
+        if let Some(contact_email) = &self.contact_email {
+            if !contact_email.contains('@') {
+                bail!("`contact_email` must be an email address, got {contact_email:?}");
+            }
+        }
+        if let Some(year) = self.year {
+            crate::calendar::validate(year, self.day.unwrap_or(1), None)
+                .context("`year`/`day` don't name a puzzle")?;
+        }
+        if !matches!(self.part, 1 | 2) {
+            bail!("`part` must be 1 or 2, got {}", self.part);
+        }
+        if self.profile.is_empty() {
+            bail!("`profile` can't be empty");
+        }
+        if let Some(template) = &self.template {
+            if !ws_root.join(template).is_file() {
+                bail!("`template` {} doesn't exist", template.display());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn contact_email(&self) -> Result<&str> {
+        match &self.contact_email {
+            Some(contact_email) => Ok(contact_email),
+            None => bail!(
+                "Set `contact_email` in {CONFIG_FILE} to your own address before talking to \
+                 adventofcode.com, so its developer can reach you about the requests this \
+                 framework sends"
+            ),
+        }
+    }
+
+    pub fn cache_dir(&self, ws_root: &Path) -> PathBuf {
+        ws_root.join(&self.cache_dir)
+    }
+
+    pub fn cached_input_path(&self, ws_root: &Path, year: i32, day: u8) -> PathBuf {
+        self.cache_dir(ws_root)
+            .join(format!("input_{year}_{day}.txt"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(toml: &str) -> Result<Config> {
+        let ws_root = tempfile::tempdir().unwrap();
+        std::fs::write(ws_root.path().join(CONFIG_FILE), toml).unwrap();
+        Config::load(ws_root.path())
+    }
+
+    #[test]
+    fn it_requires_a_contact_email_to_talk_to_the_site() {
+        let config = load("year = 2024\n").unwrap();
+        assert!(config.contact_email().is_err());
+        assert_eq!(config.part, 1);
+        assert_eq!(config.profile, "dev");
+
+        let config = load("contact_email = \"me@example.com\"\nprofile = \"release\"\n").unwrap();
+        assert_eq!(config.contact_email().unwrap(), "me@example.com");
+        assert_eq!(config.profile, "release");
+    }
+
+    #[test]
+    fn it_rejects_invalid_settings() {
+        assert!(load("contact_email = \"nobody\"\n").is_err());
+        assert!(load("part = 3\n").is_err());
+        assert!(load("year = 2025\nday = 13\n").is_err());
+        assert!(load("template = \"missing.rs\"\n").is_err());
+        assert!(load("contact = \"me@example.com\"\n").is_err());
+    }
+}
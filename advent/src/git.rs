@@ -0,0 +1,163 @@
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use tracing::debug;
+
+/// Commits everything in `day_dir` with `message`, leaving anything else that's staged alone.
+/// Refuses before staging anything if that would commit the cache directory, a data file named like
+/// an input or a copy of the puzzle input, which isn't ours to publish. Source files and the
+/// `testcase_*` examples are fine whatever they're called.
+pub fn commit_day(
+    ws_root: &Path,
+    day_dir: &Path,
+    message: &str,
+    cache_dir: &Path,
+    input_path: &Path,
+) -> Result<()> {
+    let day_dir = day_dir.strip_prefix(ws_root).unwrap_or(day_dir);
+    let cache_dir = cache_dir.strip_prefix(ws_root).unwrap_or(cache_dir);
+    let input = std::fs::read(input_path).ok();
+
+    let to_be_added = git(ws_root, &["add", "--dry-run", "--"], day_dir)?;
+    let already_staged = git(ws_root, &["diff", "--cached", "--name-only", "--"], day_dir)?;
+    let paths = to_be_added
+        .lines()
+        .filter_map(|line| line.strip_prefix("add '")?.strip_suffix('\''))
+        .chain(already_staged.lines());
+    for path in paths {
+        let file_name = Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let is_data = !file_name.ends_with(".rs") && !file_name.starts_with("testcase_");
+        if Path::new(path).starts_with(cache_dir) || (is_data && file_name.contains("input")) {
+            bail!("Refusing to commit {path}, it looks like it belongs in the cache");
+        }
+        if input.is_some() && std::fs::read(ws_root.join(path)).ok() == input {
+            bail!("Refusing to commit {path}, it's a copy of the puzzle input");
+        }
+    }
+
+    git(ws_root, &["add", "--"], day_dir)?;
+    git(
+        ws_root,
+        &["commit", "--quiet", "-m", message, "--"],
+        day_dir,
+    )?;
+    Ok(())
+}
+
+fn git(ws_root: &Path, args: &[&str], path: &Path) -> Result<String> {
+    debug!("Running git {} {}", args.join(" "), path.display());
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(ws_root)
+        .args(args)
+        .arg(path)
+        .output()?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo() -> tempfile::TempDir {
+        let ws_root = tempfile::tempdir().unwrap();
+        for args in [
+            &["init", "--quiet"][..],
+            &["config", "user.name", "Advent"],
+            &["config", "user.email", "advent@example.com"],
+        ] {
+            std::process::Command::new("git")
+                .arg("-C")
+                .arg(ws_root.path())
+                .args(args)
+                .status()
+                .unwrap();
+        }
+        std::fs::create_dir_all(ws_root.path().join("2024/17/src")).unwrap();
+        std::fs::write(ws_root.path().join("2024/17/src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::create_dir_all(ws_root.path().join(".cache")).unwrap();
+        std::fs::write(ws_root.path().join(".cache/input_2024_17.txt"), "1 2 3\n").unwrap();
+        ws_root
+    }
+
+    fn commit(ws_root: &Path) -> Result<()> {
+        commit_day(
+            ws_root,
+            &ws_root.join("2024/17"),
+            "2024 day 17 part 2 solved (1.3ms)",
+            &ws_root.join(".cache"),
+            &ws_root.join(".cache/input_2024_17.txt"),
+        )
+    }
+
+    fn log(ws_root: &Path) -> String {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(ws_root)
+            .args(["log", "--format=%s", "--name-only"])
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn it_commits_only_the_day() {
+        let ws_root = repo();
+        std::fs::write(ws_root.path().join("notes.txt"), "unrelated\n").unwrap();
+        git(ws_root.path(), &["add", "--"], Path::new("notes.txt")).unwrap();
+
+        commit(ws_root.path()).unwrap();
+
+        assert_eq!(
+            log(ws_root.path()),
+            "2024 day 17 part 2 solved (1.3ms)\n\n2024/17/src/main.rs\n"
+        );
+    }
+
+    #[test]
+    fn it_commits_modules_named_input() {
+        let ws_root = repo();
+        std::fs::write(
+            ws_root.path().join("2024/17/src/input.rs"),
+            "pub fn parse() {}
+",
+        )
+        .unwrap();
+
+        commit(ws_root.path()).unwrap();
+
+        assert_eq!(
+            log(ws_root.path()),
+            "2024 day 17 part 2 solved (1.3ms)\n\n2024/17/src/input.rs\n2024/17/src/main.rs\n"
+        );
+    }
+
+    #[test]
+    fn it_refuses_to_commit_the_input() {
+        let ws_root = repo();
+        std::fs::write(ws_root.path().join("2024/17/input.txt"), "1 2 3\n").unwrap();
+        assert!(commit(ws_root.path()).is_err());
+
+        std::fs::remove_file(ws_root.path().join("2024/17/input.txt")).unwrap();
+        std::fs::write(ws_root.path().join("2024/17/real.txt"), "1 2 3\n").unwrap();
+        assert!(commit(ws_root.path()).is_err());
+
+        let staged = git(
+            ws_root.path(),
+            &["diff", "--cached", "--name-only"],
+            Path::new("."),
+        );
+        assert_eq!(staged.unwrap(), "");
+        assert_eq!(log(ws_root.path()), "");
+    }
+}
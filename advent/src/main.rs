@@ -1,13 +1,25 @@
 #![allow(dead_code)]
 use anyhow::bail;
-use chrono::Datelike;
 use inquire::Select;
-use std::{env, path::PathBuf};
-use tracing::{debug, info, level_filters::LevelFilter};
+use metadata::DayMetadata;
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, info, level_filters::LevelFilter, warn};
 
 mod advent_api;
-
-use clap::{Args, Parser, Subcommand};
+mod calendar;
+mod config;
+mod git;
+mod metadata;
+mod puzzle;
+mod report;
+mod source;
+mod workspace;
+
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -16,6 +28,18 @@ struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count)]
     debug: u8,
 
+    /// Download puzzle and calendar pages again instead of revalidating cached copies
+    #[arg(long, global = true)]
+    refresh: bool,
+
+    /// Root of the advent workspace. Found from the current directory by default
+    #[arg(long, global = true)]
+    workspace: Option<PathBuf>,
+
+    /// Fail instead of prompting for missing arguments. Implied when stdin isn't a terminal
+    #[arg(long, global = true)]
+    no_input: bool,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -40,9 +64,9 @@ enum Command {
     },
     /// Submits output from the current challenge
     Submit {
-        /// Which part to submit
-        #[arg(short, long, default_value_t = 1)]
-        part: u8,
+        /// Which part to submit. Defaults to `part` in advent.toml, or 1
+        #[arg(short, long)]
+        part: Option<u8>,
 
         /// File to pass to the advent as input
         #[arg(short, long)]
@@ -53,9 +77,9 @@ enum Command {
     },
     /// Like submit, but without submitting the answer.
     Run {
-        /// Which part to submit
-        #[arg(short, long, default_value_t = 1)]
-        part: u8,
+        /// Which part to submit. Defaults to `part` in advent.toml, or 1
+        #[arg(short, long)]
+        part: Option<u8>,
 
         /// File to pass to the advent as input
         #[arg(short, long)]
@@ -67,9 +91,38 @@ enum Command {
     /// Save authentication cookie to allow for automatically retrieving your
     /// challenge inputs and attempting challenges.
     Authenticate { session_cookie: String },
+    /// Show stars and split times for each day of a year
+    Status {
+        /// Which year of the advent. Defaults to the current directory's, then advent.toml's,
+        /// then the most recent
+        #[arg(short, long)]
+        year: Option<i32>,
+    },
+    /// Print a completion script for the `advent` binary. `--year` and `--day` complete to the
+    /// day directories in the workspace at the time of completing. The `cargo advent` alias isn't
+    /// covered, since cargo completes its aliases' arguments itself
+    Completions { shell: clap_complete::Shell },
+    /// Print a man page for the `advent` binary
+    Man,
+    /// Summarise a year's stars, answers, run times, split times and dependencies
+    Report {
+        /// Which year of the advent
+        #[arg(short, long)]
+        year: i32,
+
+        /// Output format. `markdown` rewrites the year's table in the workspace README
+        #[arg(short, long, value_enum, default_value_t = report::Format::Markdown)]
+        format: report::Format,
+
+        /// Leave recorded answers out of the report
+        #[arg(long)]
+        redact: bool,
+    },
 }
 
 fn main() -> anyhow::Result<()> {
+    // Answers the completion scripts' `COMPLETE=<shell> advent -- ...` calls, then exits.
+    clap_complete::CompleteEnv::with_factory(completion_command).complete();
     let cli = Cli::parse();
 
     let log_level = if cli.debug > 0 {
@@ -86,18 +139,29 @@ fn main() -> anyhow::Result<()> {
 
     debug!("DEBUG ENABLED");
 
-    let today = chrono::Local::now().date_naive();
-    let current_year = if today.month() == 12 {
-        today.year()
-    } else {
-        today.year() - 1
+    let available_years = calendar::available_years(chrono::Utc::now());
+    // Only found once a command needs it, so `man` and `completions` work outside a checkout.
+    let workspace = || -> anyhow::Result<(PathBuf, config::Config)> {
+        let ws_root = workspace_root(cli.workspace.as_deref())?;
+        let config = config::Config::load(&ws_root)?;
+        Ok((ws_root, config))
     };
-    let available_years: Vec<_> = (2015..=current_year).rev().collect();
-    let ws_root = workspace_root();
+    let interactive = !cli.no_input && std::io::stdin().is_terminal();
 
     match cli.command {
         Command::New { date } => {
-            let year = if let Some(year) = date.year {
+            let (ws_root, config) = workspace()?;
+            let year = date.year.or(config.year);
+            let day = date.day.or(config.day);
+            if !interactive && (year.is_none() || day.is_none()) {
+                // Prompting would hang or fail without someone to answer.
+                return Err(missing_arguments(&[
+                    (year.is_none(), "--year"),
+                    (day.is_none(), "--day"),
+                ]));
+            }
+
+            let year = if let Some(year) = year {
                 year
             } else {
                 // use current/most recent year by default
@@ -121,7 +185,7 @@ fn main() -> anyhow::Result<()> {
                     .prompt()?
             };
 
-            let day = if let Some(day) = date.day {
+            let day = if let Some(day) = day {
                 day
             } else {
                 let year_dir = &ws_root.join(year.to_string());
@@ -131,21 +195,22 @@ fn main() -> anyhow::Result<()> {
                 let available_days = if !year_dir.exists() {
                     debug!("creating directory [{}]", year_dir.display());
                     std::fs::create_dir(year_dir)?;
-                    (1..=25).collect()
+                    calendar::days(year).collect()
                 } else {
-                    (1..=25)
+                    calendar::days(year)
                         .filter(|n| !year_dir.join(n.to_string()).is_dir())
                         .collect()
                 };
                 Select::new("Day:", available_days).prompt()?
             };
+            calendar::validate(year, day, None)?;
 
             // Only important for the relative pathing.
             // It's possible to just figure out what the correct relative path is from CWD, but
             // this is just easier.
             std::env::set_current_dir(&ws_root)?;
 
-            let mut child = std::process::Command::new(std::env::var("CARGO").unwrap())
+            let mut child = workspace::cargo()
                 .arg("new")
                 .arg(ws_root.join(year.to_string()).join(day.to_string()))
                 .arg("--name")
@@ -153,7 +218,7 @@ fn main() -> anyhow::Result<()> {
                 .spawn()?;
             child.wait()?;
 
-            let mut child = std::process::Command::new(std::env::var("CARGO").unwrap())
+            let mut child = workspace::cargo()
                 .arg("add")
                 .arg("--path")
                 .arg("common")
@@ -161,21 +226,19 @@ fn main() -> anyhow::Result<()> {
                 .arg(format!("advent_{year}_{day}"))
                 .spawn()?;
             child.wait()?;
-            let mut child = std::process::Command::new(std::env::var("CARGO").unwrap())
-                .arg("add")
-                .arg("anyhow")
-                .arg("--package")
-                .arg(format!("advent_{year}_{day}"))
-                .spawn()?;
-            child.wait()?;
-            let mut child = std::process::Command::new(std::env::var("CARGO").unwrap())
-                .arg("add")
-                .arg("tracing")
-                .arg("--package")
-                .arg(format!("advent_{year}_{day}"))
-                .spawn()?;
-            child.wait()?;
-            let main_template = include_bytes!("../solution_template.rs");
+            if !config.crates.is_empty() {
+                let mut child = workspace::cargo()
+                    .arg("add")
+                    .args(&config.crates)
+                    .arg("--package")
+                    .arg(format!("advent_{year}_{day}"))
+                    .spawn()?;
+                child.wait()?;
+            }
+            let main_template = match &config.template {
+                Some(template) => std::fs::read(ws_root.join(template))?,
+                None => include_bytes!("../solution_template.rs").to_vec(),
+            };
             std::fs::write(
                 ws_root
                     .join(year.to_string())
@@ -184,6 +247,13 @@ fn main() -> anyhow::Result<()> {
                     .join("main.rs"),
                 main_template,
             )?;
+            DayMetadata::update(
+                &ws_root.join(year.to_string()).join(day.to_string()),
+                |metadata| {
+                    metadata.unlocked_at = Some(calendar::unlock_time(year, day));
+                    metadata.scaffolded_at = Some(chrono::Utc::now());
+                },
+            )?;
         }
 
         Command::Submit {
@@ -191,79 +261,66 @@ fn main() -> anyhow::Result<()> {
             date,
             input_file,
         } => {
-            let Some(session_cookie) = cached_session_cookie()? else {
-                bail!("Can't submit without a session cookie. Configure one with the `authenticate` command")
-            };
+            let (ws_root, config) = workspace()?;
+            let part = part.unwrap_or(config.part);
+            let (year, day) = resolve_date(&date, &config, &ws_root)?;
+            calendar::validate(year, day, Some(part))?;
+            ensure_implemented(year, day, part, &ws_root)?;
+
+            let identity = identity(&config, &ws_root)?;
+            let input_file = open_input(input_file, year, day, &identity, &config, &ws_root)?;
+            let (answer, runtime) = run_solution(year, day, part, input_file, &config)?;
+            let day_dir = ws_root.join(year.to_string()).join(day.to_string());
+            DayMetadata::update(&day_dir, |metadata| {
+                metadata.part_mut(part).runtime_ms = Some(runtime.as_secs_f64() * 1000.0)
+            })?;
 
-            let ws_root = workspace_root();
-
-            let (year, day) = match (date.year, date.day) {
-                (Some(year), Some(day)) => (year, day),
-                (None, None) => {
-                    let cwd = std::env::current_dir()?;
-                    let mut dir_components = cwd
-                        .strip_prefix(&ws_root)
-                        .expect("This must be run inside the advent workspace")
-                        .components();
-                    if let (
-                        Some(std::path::Component::Normal(year_dir)),
-                        Some(std::path::Component::Normal(day_dir)),
-                    ) = (dir_components.next(), dir_components.next())
-                    {
-                        (
-                            year_dir.to_string_lossy().parse::<i32>()?,
-                            day_dir.to_string_lossy().parse::<u8>()?,
-                        )
-                    } else {
-                        bail!("Unable to determine which advent to run");
+            debug!("Solution ran without error, submitting: {answer} for part {part}...");
+            let result = advent_api::submit_answer(day, year, part, &answer, &identity)?;
+            match result {
+                advent_api::AdventResult::Correct => {
+                    let metadata = DayMetadata::update(&day_dir, |metadata| {
+                        metadata
+                            .unlocked_at
+                            .get_or_insert(calendar::unlock_time(year, day));
+                        let part = metadata.part_mut(part);
+                        part.answer = Some(answer);
+                        part.accepted_at = Some(chrono::Utc::now());
+                    })?;
+                    println!("Success!");
+                    if let Some(split) = metadata.split(part) {
+                        let mut message = format!(
+                            "Part {part} solved {} after unlock",
+                            metadata::format_split(split)
+                        );
+                        if let Some(time_spent) = metadata.time_spent(part) {
+                            let since = if part == 1 { "starting" } else { "part 1" };
+                            message +=
+                                &format!(", {} after {since}", metadata::format_split(time_spent));
+                        }
+                        println!("{message}");
                     }
-                }
-                (None, Some(day)) => (std::env::current_dir()?
-                    .strip_prefix(&ws_root)
-                    .ok()
-                    .and_then(|active_year| active_year.components().next())
-                    .and_then(|active_year| {
-                        if let std::path::Component::Normal(active_year) = active_year {
-                            active_year.to_string_lossy().parse::<i32>().ok()
-                        } else {
-                            None
+                    if config.git.commit_on_success {
+                        let message = format!(
+                            "{year} day {day} part {part} solved ({})",
+                            report::format_ms(runtime.as_secs_f64() * 1000.0)
+                        );
+                        if let Err(e) = git::commit_day(
+                            &ws_root,
+                            &day_dir,
+                            &message,
+                            &config.cache_dir(&ws_root),
+                            &config.cached_input_path(&ws_root, year, day),
+                        ) {
+                            warn!("Didn't commit the solution: {e:#}");
                         }
-                    }).expect("Couldn't determine which year to run from current directory or args. Aborting."), day
-                ),
-                (Some(_), None) => bail!("If --year is specified, --day is also required"),
-            };
-
-            let input_file = if let Some(input_file) = input_file {
-                std::fs::File::open(input_file)?
-            } else {
-                let cache_dir = ws_root.join(".cache");
-                if !cache_dir.is_dir() {
-                    std::fs::create_dir(&cache_dir)?;
-                }
-                let cached_input_path = cache_dir.join(format!("input_{year}_{day}.txt"));
-                if !cached_input_path.exists() {
-                    info!("Input file not found for advent {year} day {day}, downloading...");
-                    let input = advent_api::get_input(day, year, &session_cookie)?;
-                    std::fs::write(&cached_input_path, input)?;
+                    }
+                    if part == 1 {
+                        if let Err(e) = part_two_handoff(year, day, &identity, &config, &ws_root) {
+                            warn!("Couldn't fetch part 2: {e:#}");
+                        }
+                    }
                 }
-                std::fs::File::open(cached_input_path)?
-            };
-
-            debug!("Going to run: cargo run --package advent_{year}_{day}");
-            let output = std::process::Command::new(std::env::var("CARGO").unwrap())
-                .arg("run")
-                .arg("--package")
-                .arg(format!("advent_{year}_{day}"))
-                .arg("--")
-                .arg(format!("--part={part}"))
-                .stdin(input_file)
-                .output()?;
-            let answer = String::from_utf8(output.stdout)?;
-            debug!("Solution ran without error, submitting: {answer} for part {part}...");
-            let result =
-                advent_api::submit_answer(day, year, part, answer.trim(), &session_cookie)?;
-            match result {
-                advent_api::AdventResult::Correct => println!("Success!"),
                 advent_api::AdventResult::Incorrect(r) => println!("{r}"),
                 advent_api::AdventResult::RateLimit(r) => {
                     println!("Too many submissions. Please wait {r} before trying again")
@@ -279,103 +336,363 @@ fn main() -> anyhow::Result<()> {
             date,
             input_file,
         } => {
-            let Some(session_cookie) = cached_session_cookie()? else {
-                bail!("Can't submit without a session cookie. Configure one with the `authenticate` command")
-            };
-
-            let ws_root = workspace_root();
-
-            let (year, day) = match (date.year, date.day) {
-                (Some(year), Some(day)) => (year, day),
-                (None, None) => {
-                    let cwd = std::env::current_dir()?;
-                    let mut dir_components = cwd
-                        .strip_prefix(&ws_root)
-                        .expect("This must be run inside the advent workspace")
-                        .components();
-                    if let (
-                        Some(std::path::Component::Normal(year_dir)),
-                        Some(std::path::Component::Normal(day_dir)),
-                    ) = (dir_components.next(), dir_components.next())
-                    {
-                        (
-                            year_dir.to_string_lossy().parse::<i32>()?,
-                            day_dir.to_string_lossy().parse::<u8>()?,
-                        )
-                    } else {
-                        bail!("Unable to determine which advent to run");
-                    }
+            let (ws_root, config) = workspace()?;
+            let part = part.unwrap_or(config.part);
+            let (year, day) = resolve_date(&date, &config, &ws_root)?;
+            calendar::validate(year, day, Some(part))?;
+            ensure_implemented(year, day, part, &ws_root)?;
+
+            let identity = identity(&config, &ws_root)?;
+            let input_file = open_input(input_file, year, day, &identity, &config, &ws_root)?;
+            let (answer, runtime) = run_solution(year, day, part, input_file, &config)?;
+            DayMetadata::update(
+                &ws_root.join(year.to_string()).join(day.to_string()),
+                |metadata| {
+                    metadata.part_mut(part).runtime_ms = Some(runtime.as_secs_f64() * 1000.0)
+                },
+            )?;
+            println!("Solution ran without error, produced: {answer} for part {part}");
+        }
+        Command::Status { year } => {
+            let (ws_root, config) = workspace()?;
+            let year = year
+                .or(date_from_dir(&std::env::current_dir()?, &ws_root).0)
+                .or(config.year)
+                .or(available_years.first().copied())
+                .unwrap_or(calendar::FIRST_YEAR);
+            let report = report::Report::collect(year, &ws_root, None, false)?;
+            print!("{}", report.to_status());
+        }
+        Command::Report {
+            year,
+            format,
+            redact,
+        } => {
+            let (ws_root, config) = workspace()?;
+            let stars = match cached_session_cookie(&config, &ws_root)? {
+                Some(session_cookie) => {
+                    let identity = advent_api::Identity {
+                        session_cookie,
+                        contact_email: config.contact_email()?.to_string(),
+                    };
+                    let cache = http_cache(&config, &ws_root, cli.refresh);
+                    Some(advent_api::get_stars(year, &identity, &cache)?)
                 }
-                (None, Some(day)) => (std::env::current_dir()?
-                    .strip_prefix(&ws_root)
-                    .ok()
-                    .and_then(|active_year| active_year.components().next())
-                    .and_then(|active_year| {
-                        if let std::path::Component::Normal(active_year) = active_year {
-                            active_year.to_string_lossy().parse::<i32>().ok()
-                        } else {
-                            None
-                        }
-                    }).expect("Couldn't determine which year to run from current directory or args. Aborting."), day
-                ),
-                (Some(_), None) => bail!("If --year is specified, --day is also required"),
+                None => None,
             };
-
-            let input_file = if let Some(input_file) = input_file {
-                std::fs::File::open(input_file)?
-            } else {
-                let cache_dir = ws_root.join(".cache");
-                if !cache_dir.is_dir() {
-                    std::fs::create_dir(&cache_dir)?;
-                }
-                let cached_input_path = cache_dir.join(format!("input_{year}_{day}.txt"));
-                if !cached_input_path.exists() {
-                    info!("Input file not found for advent {year} day {day}, downloading...");
-                    let input = advent_api::get_input(day, year, &session_cookie)?;
-                    std::fs::write(&cached_input_path, input)?;
+            let report = report::Report::collect(year, &ws_root, stars.as_ref(), redact)?;
+            match format {
+                report::Format::Json => println!("{}", report.to_json()?),
+                report::Format::Csv => print!("{}", report.to_csv()),
+                report::Format::Markdown => {
+                    let readme_path = ws_root.join("README.md");
+                    let readme = std::fs::read_to_string(&readme_path)?;
+                    std::fs::write(&readme_path, report.update_readme(&readme))?;
+                    info!("Updated the {year} table in {}", readme_path.display());
                 }
-                std::fs::File::open(cached_input_path)?
+            }
+        }
+        Command::Completions { shell } => {
+            let shells = clap_complete::env::Shells::builtins();
+            let Some(completer) = shells.completer(&shell.to_string()) else {
+                bail!("No dynamic completions for {shell}");
             };
-
-            debug!("Going to run: cargo run --package advent_{year}_{day}");
-            let output = std::process::Command::new(std::env::var("CARGO").unwrap())
-                .arg("run")
-                .arg("--package")
-                .arg(format!("advent_{year}_{day}"))
-                .arg("--")
-                .arg(format!("--part={part}"))
-                .stdin(input_file)
-                .output()?;
-            let answer = String::from_utf8(output.stdout)?;
-            println!("Solution ran without error, produced: {answer} for part {part}");
+            let bin = std::env::current_exe()?;
+            completer.write_registration(
+                "COMPLETE",
+                "advent",
+                "advent",
+                &bin.to_string_lossy(),
+                &mut std::io::stdout(),
+            )?;
+        }
+        Command::Man => {
+            clap_mangen::Man::new(Cli::command()).render(&mut std::io::stdout())?;
         }
         Command::Authenticate { session_cookie } => {
-            std::fs::write(session_cookie_cache_path(), session_cookie)?;
+            // Outside a checkout the cookie is kept for the user, for whichever workspace they use.
+            let path = match workspace_root(cli.workspace.as_deref()) {
+                Ok(ws_root) => {
+                    session_cookie_cache_path(&config::Config::load(&ws_root)?, &ws_root)
+                }
+                Err(_) => user_session_cookie_path()?,
+            };
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, session_cookie)?;
+            info!("Saved the session cookie to {}", path.display());
         }
     }
 
     Ok(())
 }
 
-fn cached_session_cookie() -> anyhow::Result<Option<String>> {
-    let session_cookie_cache_path = session_cookie_cache_path();
-    if session_cookie_cache_path.exists() {
-        let session_cookie =
-            String::from(std::fs::read_to_string(session_cookie_cache_path)?.trim());
-        Ok(Some(session_cookie))
-    } else {
-        Ok(None)
+/// The CLI definition with `--year` and `--day` completing to the day directories in the workspace
+/// containing the current directory, looked up each time. `new` is left alone, as it's for days
+/// that don't exist yet.
+fn completion_command() -> clap::Command {
+    let mut command = Cli::command();
+    let subcommands = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .filter(|name| name != "new")
+        .collect::<Vec<_>>();
+    for name in subcommands {
+        command = command.mut_subcommand(name, |mut subcommand| {
+            for (id, complete) in [
+                ("year", complete_years as fn(&std::ffi::OsStr) -> _),
+                ("day", complete_days),
+            ] {
+                if subcommand.get_arguments().any(|arg| arg.get_id() == id) {
+                    subcommand =
+                        subcommand.mut_arg(id, |arg| arg.add(ArgValueCompleter::new(complete)));
+                }
+            }
+            subcommand
+        });
+    }
+    command
+}
+
+fn complete_years(current: &std::ffi::OsStr) -> Vec<CompletionCandidate> {
+    complete_numbers(current, |(year, _)| year.to_string())
+}
+
+fn complete_days(current: &std::ffi::OsStr) -> Vec<CompletionCandidate> {
+    complete_numbers(current, |(_, day)| day.to_string())
+}
+
+/// The distinct `number`s of the workspace's days that start with what's been typed so far.
+fn complete_numbers(
+    current: &std::ffi::OsStr,
+    number: impl Fn((i32, u8)) -> String,
+) -> Vec<CompletionCandidate> {
+    let Ok(ws_root) = workspace_root(None) else {
+        return Vec::new();
+    };
+    let current = current.to_string_lossy();
+    let mut numbers = workspace::days(&ws_root)
+        .into_iter()
+        .map(number)
+        .filter(|n| n.starts_with(current.as_ref()))
+        .collect::<Vec<_>>();
+    numbers.sort_by_key(|n| n.parse::<i32>().unwrap_or_default());
+    numbers.dedup();
+    numbers.into_iter().map(CompletionCandidate::new).collect()
+}
+
+/// Pulls the part 2 description into the day's `puzzle.md`, saves any new examples, and prints the
+/// new prompt so there's no need to switch to the browser.
+fn part_two_handoff(
+    year: i32,
+    day: u8,
+    identity: &advent_api::Identity,
+    config: &config::Config,
+    ws_root: &Path,
+) -> anyhow::Result<()> {
+    // Part 2 was just unlocked, so whatever is cached is known to be stale.
+    let cache = http_cache(config, ws_root, true);
+    let page = advent_api::get_puzzle_page(day, year, identity, &cache)?;
+    let puzzle = puzzle::Puzzle::from_html(&page);
+    let day_dir = ws_root.join(year.to_string()).join(day.to_string());
+    std::fs::write(day_dir.join("puzzle.md"), puzzle.to_markdown())?;
+    puzzle.save_new_examples(&day_dir)?;
+    if let Some(part_two) = puzzle.parts.get(1) {
+        println!("\n{part_two}");
+    }
+    Ok(())
+}
+
+/// Works out which day to run from the arguments, falling back to the current directory and then
+/// `advent.toml` for whatever they leave out.
+fn resolve_date(
+    date: &DateArgs,
+    config: &config::Config,
+    ws_root: &Path,
+) -> anyhow::Result<(i32, u8)> {
+    let (dir_year, dir_day) = date_from_dir(&std::env::current_dir()?, ws_root);
+    let (year, day) = match date.year {
+        // A day directory for some other year says nothing about which day is wanted.
+        Some(year) => (Some(year), date.day.or(config.day)),
+        None => (
+            dir_year.or(config.year),
+            date.day.or(dir_day).or(config.day),
+        ),
+    };
+    match (year, day) {
+        (Some(year), Some(day)) => Ok((year, day)),
+        _ => Err(missing_arguments(&[
+            (year.is_none(), "--year"),
+            (day.is_none(), "--day"),
+        ])),
     }
 }
 
-fn workspace_root() -> PathBuf {
-    PathBuf::from(
-        PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("Is this not a cargo workspace?"))
-            .parent()
-            .unwrap(),
+/// Reads the year and day from a `<year>/<day>` path inside the workspace.
+fn date_from_dir(dir: &Path, ws_root: &Path) -> (Option<i32>, Option<u8>) {
+    let Ok(relative_dir) = dir.strip_prefix(ws_root) else {
+        return (None, None);
+    };
+    let mut dir_components = relative_dir
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy());
+    let year = dir_components.next().and_then(|year| year.parse().ok());
+    let day = year
+        .and(dir_components.next())
+        .and_then(|day| day.parse().ok());
+    (year, day)
+}
+
+/// An error listing the arguments that couldn't be worked out, where `arguments` pairs whether each
+/// one is missing with its flag.
+fn missing_arguments(arguments: &[(bool, &str)]) -> anyhow::Error {
+    let missing = arguments
+        .iter()
+        .filter_map(|&(is_missing, flag)| is_missing.then_some(flag))
+        .collect::<Vec<_>>();
+    anyhow::anyhow!(
+        "Missing {}. Pass them as arguments or set defaults in advent.toml",
+        missing.join(" and ")
     )
 }
 
-fn session_cookie_cache_path() -> PathBuf {
-    workspace_root().join(".cache").join("session_cookie.txt")
+/// Fails early if the day's `part` is still a `todo!()` stub, rather than building and running it
+/// only for it to panic. Days written as a `common::Solution` keep their parts in `lib.rs`.
+fn ensure_implemented(year: i32, day: u8, part: u8, ws_root: &Path) -> anyhow::Result<()> {
+    let src_dir = ws_root
+        .join(year.to_string())
+        .join(day.to_string())
+        .join("src");
+    let main_path = [src_dir.join("lib.rs"), src_dir.join("main.rs")]
+        .into_iter()
+        .find(|path| path.exists())
+        .unwrap_or_else(|| src_dir.join("main.rs"));
+    let Ok(main_source) = std::fs::read_to_string(&main_path) else {
+        // Let cargo explain what's wrong with the package.
+        return Ok(());
+    };
+    if source::is_unimplemented(&main_source, part) {
+        bail!(
+            "Part {part} not implemented: `part{part}` in {} is still a `todo!()`",
+            main_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Opens `input_file` if one was given, otherwise the cached puzzle input, downloading it first if
+/// needed.
+fn open_input(
+    input_file: Option<PathBuf>,
+    year: i32,
+    day: u8,
+    identity: &advent_api::Identity,
+    config: &config::Config,
+    ws_root: &Path,
+) -> anyhow::Result<std::fs::File> {
+    if let Some(input_file) = input_file {
+        return Ok(std::fs::File::open(input_file)?);
+    }
+    let cache_dir = config.cache_dir(ws_root);
+    if !cache_dir.is_dir() {
+        std::fs::create_dir_all(&cache_dir)?;
+    }
+    let cached_input_path = config.cached_input_path(ws_root, year, day);
+    if !cached_input_path.exists() {
+        info!("Input file not found for advent {year} day {day}, downloading...");
+        let input = advent_api::get_input(day, year, identity)?;
+        std::fs::write(&cached_input_path, input)?;
+    }
+    Ok(std::fs::File::open(cached_input_path)?)
+}
+
+/// Builds the day's solution, then runs it on `input_file`, returning the trimmed answer and how
+/// long the solution binary took. The build is kept out of the timing.
+fn run_solution(
+    year: i32,
+    day: u8,
+    part: u8,
+    input_file: std::fs::File,
+    config: &config::Config,
+) -> anyhow::Result<(String, std::time::Duration)> {
+    let package = format!("advent_{year}_{day}");
+    let executable = workspace::build(&config.profile, &package)?;
+
+    let start = std::time::Instant::now();
+    let output = std::process::Command::new(executable)
+        .arg(format!("--part={part}"))
+        .stdin(input_file)
+        .output()?;
+    let runtime = start.elapsed();
+    if !output.status.success() {
+        bail!(
+            "{package} failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok((
+        String::from_utf8(output.stdout)?.trim().to_string(),
+        runtime,
+    ))
+}
+
+/// The session cookie saved by `authenticate` along with the contact email from advent.toml.
+fn identity(config: &config::Config, ws_root: &Path) -> anyhow::Result<advent_api::Identity> {
+    let Some(session_cookie) = cached_session_cookie(config, ws_root)? else {
+        bail!(
+            "Can't submit without a session cookie. Configure one with the `authenticate` command"
+        )
+    };
+    Ok(advent_api::Identity {
+        session_cookie,
+        contact_email: config.contact_email()?.to_string(),
+    })
+}
+
+/// The workspace's own session cookie, or else the one saved for the user by `authenticate` outside
+/// a checkout.
+fn cached_session_cookie(
+    config: &config::Config,
+    ws_root: &Path,
+) -> anyhow::Result<Option<String>> {
+    let user_path = user_session_cookie_path().ok();
+    for path in std::iter::once(session_cookie_cache_path(config, ws_root)).chain(user_path) {
+        if path.exists() {
+            let session_cookie = String::from(std::fs::read_to_string(path)?.trim());
+            return Ok(Some(session_cookie));
+        }
+    }
+    Ok(None)
+}
+
+fn session_cookie_cache_path(config: &config::Config, ws_root: &Path) -> PathBuf {
+    config.cache_dir(ws_root).join("session_cookie.txt")
+}
+
+/// Where `authenticate` keeps the session cookie when it isn't run inside a workspace.
+fn user_session_cookie_path() -> anyhow::Result<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    let Some(config_dir) = config_dir else {
+        bail!(
+            "Couldn't find a config directory for the session cookie. Run this from your advent \
+             workspace or pass --workspace"
+        );
+    };
+    Ok(config_dir.join("advent").join("session_cookie.txt"))
+}
+
+/// `--workspace` if it was given, otherwise the workspace containing the current directory.
+fn workspace_root(workspace: Option<&Path>) -> anyhow::Result<PathBuf> {
+    match workspace {
+        Some(ws_root) => Ok(ws_root.canonicalize()?),
+        None => workspace::find_root(&std::env::current_dir()?),
+    }
+}
+
+fn http_cache(config: &config::Config, ws_root: &Path, refresh: bool) -> advent_api::HttpCache {
+    advent_api::HttpCache::new(config.cache_dir(ws_root).join("http"), refresh)
 }
@@ -0,0 +1,123 @@
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+const METADATA_FILE: &str = "metadata.json";
+
+/// Everything recorded about a day that can't be recovered from its source. It lives next to the
+/// solution as `metadata.json` so it can be committed along with it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DayMetadata {
+    /// When the puzzle became available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlocked_at: Option<DateTime<Utc>>,
+
+    /// When `cargo advent new` created the day's crate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scaffolded_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parts: BTreeMap<u8, PartMetadata>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PartMetadata {
+    /// The answer the site accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+
+    /// When the site accepted `answer`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted_at: Option<DateTime<Utc>>,
+
+    /// How long the solution binary took on its most recent run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime_ms: Option<f64>,
+}
+
+impl DayMetadata {
+    pub fn load(day_dir: &Path) -> Result<Self> {
+        let path = day_dir.join(METADATA_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(&std::fs::read_to_string(&path)?)
+            .with_context(|| format!("{} is not valid day metadata", path.display()))
+    }
+
+    pub fn save(&self, day_dir: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(day_dir.join(METADATA_FILE), json + "\n")?;
+        Ok(())
+    }
+
+    /// Loads the day's metadata, applies `f` and writes it back, returning the updated metadata.
+    pub fn update(day_dir: &Path, f: impl FnOnce(&mut Self)) -> Result<Self> {
+        let mut metadata = Self::load(day_dir)?;
+        f(&mut metadata);
+        metadata.save(day_dir)?;
+        Ok(metadata)
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartMetadata> {
+        self.parts.get(&part)
+    }
+
+    pub fn part_mut(&mut self, part: u8) -> &mut PartMetadata {
+        self.parts.entry(part).or_default()
+    }
+
+    /// Time from the puzzle unlocking until `part` was accepted, like the leaderboard measures it.
+    pub fn split(&self, part: u8) -> Option<TimeDelta> {
+        Some(self.part(part)?.accepted_at? - self.unlocked_at?)
+    }
+
+    /// Time from scaffolding the day, or from the previous part being accepted, until `part` was
+    /// accepted.
+    pub fn time_spent(&self, part: u8) -> Option<TimeDelta> {
+        let started_at = match part {
+            1 => self.scaffolded_at?,
+            part => self.part(part - 1)?.accepted_at?,
+        };
+        Some(self.part(part)?.accepted_at? - started_at)
+    }
+}
+
+/// Formats a split as `H:MM:SS`, letting the hours run past a day like the leaderboard's `>24h`
+/// doesn't.
+pub fn format_split(split: TimeDelta) -> String {
+    let seconds = split.num_seconds().max(0);
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_measures_splits_from_unlock_and_from_the_last_step() {
+        let unlocked_at = crate::calendar::unlock_time(2024, 17);
+        let mut metadata = DayMetadata {
+            unlocked_at: Some(unlocked_at),
+            scaffolded_at: Some(unlocked_at + TimeDelta::seconds(90)),
+            ..Default::default()
+        };
+        metadata.part_mut(1).accepted_at = Some(unlocked_at + TimeDelta::seconds(754));
+        metadata.part_mut(2).accepted_at = Some(unlocked_at + TimeDelta::hours(26));
+
+        assert_eq!(metadata.split(1).map(format_split).unwrap(), "0:12:34");
+        assert_eq!(metadata.time_spent(1).map(format_split).unwrap(), "0:11:04");
+        assert_eq!(metadata.split(2).map(format_split).unwrap(), "26:00:00");
+        assert_eq!(
+            metadata.time_spent(2).map(format_split).unwrap(),
+            "25:47:26"
+        );
+    }
+}
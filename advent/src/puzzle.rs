@@ -0,0 +1,142 @@
+use std::path::Path;
+
+use anyhow::Result;
+use scraper::{ElementRef, Html, Node, Selector};
+use tracing::info;
+
+/// The parts of a puzzle page that are useful offline.
+#[derive(Debug)]
+pub struct Puzzle {
+    /// One Markdown document per unlocked part.
+    pub parts: Vec<String>,
+    /// Contents of every `<pre><code>` block, in page order.
+    pub examples: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn from_html(html: &str) -> Self {
+        let doc = Html::parse_document(html);
+        let article_selector = Selector::parse("article.day-desc").unwrap();
+        let example_selector = Selector::parse("pre > code").unwrap();
+        let parts = doc
+            .select(&article_selector)
+            .map(|article| markdown(article).trim().to_string())
+            .collect();
+        let examples = doc
+            .select(&article_selector)
+            .flat_map(|article| article.select(&example_selector))
+            .map(|code| code.text().collect())
+            .collect();
+        Self { parts, examples }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.parts.join("\n\n") + "\n"
+    }
+
+    /// Writes each example that isn't already in one of the `testcase_N.txt` files in `day_dir` to
+    /// the next unused `testcase_N.txt`, returning the paths written.
+    pub fn save_new_examples(&self, day_dir: &Path) -> Result<Vec<std::path::PathBuf>> {
+        let mut existing = Vec::new();
+        let mut n = 1;
+        while day_dir.join(format!("testcase_{n}.txt")).exists() {
+            existing.push(std::fs::read_to_string(
+                day_dir.join(format!("testcase_{n}.txt")),
+            )?);
+            n += 1;
+        }
+
+        let mut written = Vec::new();
+        for example in &self.examples {
+            if existing.iter().any(|e| e.trim_end() == example.trim_end()) {
+                continue;
+            }
+            let path = day_dir.join(format!("testcase_{n}.txt"));
+            std::fs::write(&path, example)?;
+            info!("Saved example to {}", path.display());
+            existing.push(example.clone());
+            written.push(path);
+            n += 1;
+        }
+        Ok(written)
+    }
+}
+
+fn markdown(element: ElementRef) -> String {
+    // Whitespace between block elements is just HTML formatting, but between inline elements it
+    // separates words.
+    let is_block = matches!(element.value().name(), "article" | "ul");
+    let inner = || {
+        element
+            .children()
+            .filter_map(|child| match child.value() {
+                Node::Text(text) if is_block && text.trim().is_empty() => None,
+                Node::Text(text) => Some(text.to_string()),
+                Node::Element(_) => ElementRef::wrap(child).map(markdown),
+                _ => None,
+            })
+            .collect::<String>()
+    };
+    match element.value().name() {
+        "h2" => format!("## {}\n\n", inner()),
+        "p" => format!("{}\n\n", inner()),
+        "pre" => format!("```\n{}```\n\n", element.text().collect::<String>()),
+        "code" => format!("`{}`", inner()),
+        "em" => format!("*{}*", inner()),
+        "ul" => format!("{}\n", inner().trim_start()),
+        "li" => format!("- {}\n", inner().trim()),
+        "a" => match element.value().attr("href") {
+            Some(href) => format!("[{}]({href})", inner()),
+            None => inner(),
+        },
+        _ => inner(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Find the <em>sum</em> of <code>x</code>. For example:</p>
+<pre><code>1
+2
+</code></pre>
+<ul><li>Read <a href="/2024/day/1/input">the input</a>.</li></ul>
+</article>
+<p>Your puzzle answer was <code>3</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now multiply:</p>
+<pre><code>3
+4
+</code></pre>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn it_converts_articles_to_markdown() {
+        let puzzle = Puzzle::from_html(PAGE);
+        assert_eq!(puzzle.parts.len(), 2);
+        assert_eq!(
+            puzzle.parts[0],
+            "## --- Day 1: Test ---\n\nFind the *sum* of `x`. For example:\n\n```\n1\n2\n```\n\n- Read [the input](/2024/day/1/input).",
+        );
+        assert_eq!(
+            puzzle.parts[1],
+            "## --- Part Two ---\n\nNow multiply:\n\n```\n3\n4\n```"
+        );
+        assert_eq!(puzzle.examples, vec!["1\n2\n", "3\n4\n"]);
+    }
+
+    #[test]
+    fn it_only_saves_new_examples() {
+        let day_dir = tempfile::tempdir().unwrap();
+        std::fs::write(day_dir.path().join("testcase_1.txt"), "1\n2\n").unwrap();
+
+        let written = Puzzle::from_html(PAGE)
+            .save_new_examples(day_dir.path())
+            .unwrap();
+
+        assert_eq!(written, vec![day_dir.path().join("testcase_2.txt")]);
+        assert_eq!(std::fs::read_to_string(&written[0]).unwrap(), "3\n4\n");
+    }
+}
@@ -0,0 +1,274 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::metadata::{format_split, DayMetadata};
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayReport {
+    pub day: u8,
+    pub stars: u8,
+    pub part1_answer: Option<String>,
+    pub part2_answer: Option<String>,
+    pub part1_ms: Option<f64>,
+    pub part2_ms: Option<f64>,
+    /// Seconds from the puzzle unlocking until each part was accepted.
+    pub part1_split: Option<i64>,
+    pub part2_split: Option<i64>,
+    pub crates: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub year: i32,
+    pub days: Vec<DayReport>,
+}
+
+impl Report {
+    /// Gathers a row for every day crate in the year's directory. `stars` comes from the calendar
+    /// page when we're logged in; otherwise each recorded answer counts as a star.
+    pub fn collect(
+        year: i32,
+        ws_root: &Path,
+        stars: Option<&HashMap<u8, u8>>,
+        redact: bool,
+    ) -> Result<Self> {
+        let year_dir = ws_root.join(year.to_string());
+        let mut days = Vec::new();
+        if year_dir.is_dir() {
+            for entry in std::fs::read_dir(&year_dir)? {
+                let day_dir = entry?.path();
+                let Some(day) = day_dir
+                    .file_name()
+                    .and_then(|name| name.to_str()?.parse::<u8>().ok())
+                else {
+                    continue;
+                };
+                if !crate::calendar::days(year).contains(&day)
+                    || !day_dir.join("Cargo.toml").exists()
+                {
+                    continue;
+                }
+                days.push(DayReport::collect(day, &day_dir, stars, redact)?);
+            }
+        }
+        days.sort_by_key(|day| day.day);
+        Ok(Self { year, days })
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "day,stars,part1_answer,part2_answer,part1_ms,part2_ms,part1_split,part2_split,crates\n",
+        );
+        for day in &self.days {
+            let fields = [
+                day.day.to_string(),
+                day.stars.to_string(),
+                day.part1_answer.clone().unwrap_or_default(),
+                day.part2_answer.clone().unwrap_or_default(),
+                day.part1_ms
+                    .map(|ms| format!("{ms:.3}"))
+                    .unwrap_or_default(),
+                day.part2_ms
+                    .map(|ms| format!("{ms:.3}"))
+                    .unwrap_or_default(),
+                day.part1_split.map(|s| s.to_string()).unwrap_or_default(),
+                day.part2_split.map(|s| s.to_string()).unwrap_or_default(),
+                day.crates.join(";"),
+            ];
+            let row = fields.map(|field| csv_field(&field)).join(",");
+            csv.push_str(&row);
+            csv.push('\n');
+        }
+        csv
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from(
+            "| Day | Stars | Part 1 | Part 2 | Part 1 time | Part 2 time | Part 1 split | Part 2 split | Crates |\n\
+             | --: | :-- | --: | --: | --: | --: | --: | --: | :-- |\n",
+        );
+        for day in &self.days {
+            let cells = [
+                format!("[{0}]({1}/{0}/src/main.rs)", day.day, self.year),
+                "⭐".repeat(day.stars as usize),
+                day.part1_answer
+                    .as_deref()
+                    .map(markdown_code)
+                    .unwrap_or_default(),
+                day.part2_answer
+                    .as_deref()
+                    .map(markdown_code)
+                    .unwrap_or_default(),
+                day.part1_ms.map(format_ms).unwrap_or_default(),
+                day.part2_ms.map(format_ms).unwrap_or_default(),
+                day.part1_split.map(format_seconds).unwrap_or_default(),
+                day.part2_split.map(format_seconds).unwrap_or_default(),
+                day.crates.join(", "),
+            ];
+            table.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        table
+    }
+
+    /// A plain text overview of stars and split times for the terminal.
+    pub fn to_status(&self) -> String {
+        let mut status = format!("{}\nDay  Stars  Part 1    Part 2\n", self.year);
+        for day in &self.days {
+            let line = format!(
+                "{:>3}  {:<5}  {:<8}  {}",
+                day.day,
+                "*".repeat(day.stars as usize),
+                day.part1_split.map(format_seconds).unwrap_or_default(),
+                day.part2_split.map(format_seconds).unwrap_or_default(),
+            );
+            status.push_str(line.trim_end());
+            status.push('\n');
+        }
+        status
+    }
+
+    /// Replaces the year's table between its markers in `readme`, appending a new section if the
+    /// markers aren't there yet.
+    pub fn update_readme(&self, readme: &str) -> String {
+        let start_marker = format!("<!-- advent-report {} start -->", self.year);
+        let end_marker = format!("<!-- advent-report {} end -->", self.year);
+        let section = format!("{start_marker}\n{}{end_marker}", self.to_markdown());
+        match (readme.find(&start_marker), readme.find(&end_marker)) {
+            (Some(start), Some(end)) if start < end => format!(
+                "{}{section}{}",
+                &readme[..start],
+                &readme[end + end_marker.len()..]
+            ),
+            _ => format!("{}\n\n## {}\n\n{section}\n", readme.trim_end(), self.year),
+        }
+    }
+}
+
+impl DayReport {
+    fn collect(
+        day: u8,
+        day_dir: &Path,
+        stars: Option<&HashMap<u8, u8>>,
+        redact: bool,
+    ) -> Result<Self> {
+        let metadata = DayMetadata::load(day_dir)?;
+        let answer = |part| {
+            let answer = metadata.part(part)?.answer.clone()?;
+            Some(if redact {
+                String::from("redacted")
+            } else {
+                answer
+            })
+        };
+        let runtime_ms = |part| metadata.part(part)?.runtime_ms;
+        let split = |part| Some(metadata.split(part)?.num_seconds());
+        let recorded_stars = [1, 2]
+            .iter()
+            .filter(|&&part| metadata.part(part).is_some_and(|p| p.answer.is_some()))
+            .count() as u8;
+
+        let manifest: toml::Table = std::fs::read_to_string(day_dir.join("Cargo.toml"))?.parse()?;
+        let crates = manifest
+            .get("dependencies")
+            .and_then(toml::Value::as_table)
+            .map(|deps| deps.keys().cloned().collect())
+            .unwrap_or_default();
+
+        Ok(Self {
+            day,
+            stars: stars.map_or(recorded_stars, |stars| {
+                stars.get(&day).copied().unwrap_or_default()
+            }),
+            part1_answer: answer(1),
+            part2_answer: answer(2),
+            part1_ms: runtime_ms(1),
+            part2_ms: runtime_ms(2),
+            part1_split: split(1),
+            part2_split: split(2),
+            crates,
+        })
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn markdown_code(answer: &str) -> String {
+    format!("`{answer}`")
+}
+
+fn format_seconds(seconds: i64) -> String {
+    format_split(chrono::TimeDelta::seconds(seconds))
+}
+
+pub fn format_ms(ms: f64) -> String {
+    if ms < 1000.0 {
+        format!("{ms:.1}ms")
+    } else {
+        format!("{:.2}s", ms / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report {
+        Report {
+            year: 2024,
+            days: vec![DayReport {
+                day: 17,
+                stars: 2,
+                part1_answer: Some(String::from("4,6,3,5")),
+                part2_answer: None,
+                part1_ms: Some(1.25),
+                part2_ms: None,
+                part1_split: Some(754),
+                part2_split: None,
+                crates: vec![String::from("anyhow"), String::from("common")],
+            }],
+        }
+    }
+
+    #[test]
+    fn it_quotes_csv_fields() {
+        assert_eq!(
+            report().to_csv(),
+            "day,stars,part1_answer,part2_answer,part1_ms,part2_ms,part1_split,part2_split,crates\n\
+             17,2,\"4,6,3,5\",,1.250,,754,,anyhow;common\n"
+        );
+    }
+
+    #[test]
+    fn it_replaces_the_marked_readme_section() {
+        let report = report();
+        let readme = report.update_readme("# Advent\n");
+        assert!(readme.starts_with("# Advent\n\n## 2024\n\n<!-- advent-report 2024 start -->\n"));
+        assert!(readme.contains(
+            "| [17](2024/17/src/main.rs) | ⭐⭐ | `4,6,3,5` |  | 1.2ms |  | 0:12:34 |  | anyhow, common |\n"
+        ));
+
+        let edited = readme.replace("## 2024\n", "## 2024\n\nSome notes\n") + "\n## Footer\n";
+        let updated = report.update_readme(&edited);
+        assert_eq!(updated, edited);
+        assert_eq!(updated.matches("advent-report 2024 start").count(), 1);
+    }
+}
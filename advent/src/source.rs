@@ -0,0 +1,182 @@
+/// Whether `part1`/`part2` in a solution's source is still a stub like the template's, i.e. its body
+/// is nothing but `todo!()` or `unimplemented!()`. Running it would only panic, so there's nothing to
+/// submit. A part that does some work before reaching a `todo!()` might still answer, so it's run.
+pub fn is_unimplemented(source: &str, part: u8) -> bool {
+    part_body(source, part).is_some_and(|body| {
+        let code = body
+            .lines()
+            .map(|line| line.split_once("//").map_or(line, |(code, _)| code).trim())
+            // The template discards its input with `let _ = input;` to avoid a warning.
+            .filter(|code| !code.is_empty() && !is_discard(code))
+            .collect::<String>();
+        let call = code.strip_suffix(';').unwrap_or(&code);
+        (call.starts_with("todo!(") || call.starts_with("unimplemented!("))
+            && call.ends_with(')')
+            && !call.contains(';')
+    })
+}
+
+/// A statement like `let _ = input;`.
+fn is_discard(code: &str) -> bool {
+    code.strip_prefix("let _ =")
+        .and_then(|rest| rest.strip_suffix(';'))
+        .is_some_and(|name| {
+            let name = name.trim();
+            !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        })
+}
+
+/// The text between the braces of the free `fn part{part}`, if there's such a function. Only
+/// unindented functions count, so the forwarding methods in a day's `impl Solution` are skipped.
+fn part_body(source: &str, part: u8) -> Option<&str> {
+    let signature = format!("fn part{part}");
+    let mut offset = 0;
+    let start = source.split_inclusive('\n').find_map(|line| {
+        let line_start = offset;
+        offset += line.len();
+        let item = line
+            .strip_prefix("pub(crate) ")
+            .or_else(|| line.strip_prefix("pub "))
+            .unwrap_or(line);
+        let generics_or_params = item.strip_prefix(&signature)?;
+        (generics_or_params.starts_with('(') || generics_or_params.starts_with('<'))
+            .then_some(line_start)
+    })?;
+    let open = start + source[start..].find('{')?;
+    let close = open + matching_brace(&source[open..])?;
+    Some(&source[open + 1..close])
+}
+
+/// The index of the `}` closing the `{` that `code` starts with. Braces in comments, strings and
+/// character literals don't count.
+fn matching_brace(code: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = code.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            '/' if chars.next_if(|&(_, next)| next == '/').is_some() => {
+                chars.by_ref().find(|&(_, c)| c == '\n');
+            }
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            // A character literal rather than a lifetime: `'{'` or an escape like `'\''`.
+            '\'' => {
+                let rest = &code[i + 1..];
+                let literal_len = if rest.starts_with('\\') {
+                    rest[2..].find('\'').map(|end| end + 3)
+                } else {
+                    let mut rest_chars = rest.chars();
+                    let inner = rest_chars.next().map_or(0, char::len_utf8);
+                    rest_chars.next().filter(|&c| c == '\'').map(|_| inner + 1)
+                };
+                if let Some(len) = literal_len {
+                    while chars.next_if(|&(j, _)| j <= i + len).is_some() {}
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_the_template_stubs() {
+        let template = include_str!("../solution_template.rs");
+        assert!(is_unimplemented(template, 1));
+        assert!(is_unimplemented(template, 2));
+    }
+
+    #[test]
+    fn it_ignores_other_functions_and_comments() {
+        let source = r#"
+fn part1(input: Vec<String>) -> anyhow::Result<i64> {
+    let total = input.iter().map(|line| { line.len() as i64 }).sum();
+    // todo!() make this faster
+    Ok(total)
+}
+
+fn part2(input: Vec<String>) -> anyhow::Result<i64> {
+    // Not done yet
+    unimplemented!(
+        "part 2"
+    )
+}
+
+fn helper() {
+    todo!()
+}
+"#;
+        assert!(!is_unimplemented(source, 1));
+        assert!(is_unimplemented(source, 2));
+    }
+
+    #[test]
+    fn it_runs_partly_finished_parts() {
+        let source = r#"
+fn part1(input: Vec<String>) -> anyhow::Result<i64> {
+    if input.is_empty() {
+        return Ok(0);
+    }
+    todo!()
+}
+
+fn part2(input: Vec<String>) -> anyhow::Result<i64> {
+    let _ = input;
+    todo!(); Ok(0)
+}
+"#;
+        assert!(!is_unimplemented(source, 1));
+        assert!(!is_unimplemented(source, 2));
+    }
+
+    #[test]
+    fn it_skips_solution_methods_and_literal_braces() {
+        let source = r#"
+impl Solution for Day1 {
+    fn part1(input: &Vec<i64>) -> anyhow::Result<impl ToString> {
+        part1(input)
+    }
+}
+
+pub fn part1(input: &Vec<i64>) -> anyhow::Result<i64> {
+    let _ = input;
+    todo!("count the \"}}\" pairs")
+}
+
+fn part2<'a>(input: &'a Vec<i64>) -> anyhow::Result<i64> {
+    let _quote = '\'';
+    // }
+    Ok(input.iter().filter(|&&c| c == '{' as i64).count() as i64)
+}
+"#;
+        assert!(is_unimplemented(source, 1));
+        assert!(!is_unimplemented(source, 2));
+        assert_eq!(
+            part_body(source, 2).map(str::trim),
+            Some(
+                "let _quote = '\\'';\n    // }\n    \
+                 Ok(input.iter().filter(|&&c| c == '{' as i64).count() as i64)"
+            )
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use tracing::debug;
+
+/// Finds the advent workspace containing `start` by walking up to the first `Cargo.toml` that
+/// declares a `[workspace]`. Day crates have their own manifests, so the nearest one isn't enough.
+pub fn find_root(start: &Path) -> Result<PathBuf> {
+    for dir in start.ancestors() {
+        let manifest_path = dir.join("Cargo.toml");
+        if !manifest_path.is_file() {
+            continue;
+        }
+        let manifest: toml::Table = std::fs::read_to_string(&manifest_path)?.parse()?;
+        if manifest.contains_key("workspace") {
+            return Ok(dir.to_path_buf());
+        }
+    }
+    bail!(
+        "{} is not inside a cargo workspace. Run this from your advent workspace or pass --workspace",
+        start.display()
+    )
+}
+
+/// Every `<year>/<day>` directory in the workspace, in order.
+pub fn days(ws_root: &Path) -> Vec<(i32, u8)> {
+    fn numbered_dirs<N: std::str::FromStr>(dir: &Path) -> Vec<(N, PathBuf)> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let n = path.file_name()?.to_str()?.parse().ok()?;
+                path.is_dir().then_some((n, path))
+            })
+            .collect()
+    }
+
+    let mut days = numbered_dirs::<i32>(ws_root)
+        .into_iter()
+        .flat_map(|(year, year_dir)| {
+            numbered_dirs::<u8>(&year_dir)
+                .into_iter()
+                .map(move |(day, _)| (year, day))
+        })
+        .collect::<Vec<_>>();
+    days.sort();
+    days
+}
+
+/// The cargo that invoked us when run as `cargo advent`, otherwise whichever is on the `PATH`.
+pub fn cargo() -> std::process::Command {
+    std::process::Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+}
+
+/// Builds `package`'s binary with `profile` and returns where cargo put it, wherever the target
+/// directory is configured to be.
+pub fn build(profile: &str, package: &str) -> Result<PathBuf> {
+    debug!("Going to build: cargo build --profile {profile} --package {package}");
+    let output = cargo()
+        .arg("build")
+        .arg("--quiet")
+        .arg("--message-format=json-render-diagnostics")
+        .arg("--profile")
+        .arg(profile)
+        .arg("--package")
+        .arg(package)
+        .stderr(std::process::Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        bail!("Failed to build {package}");
+    }
+    match built_executable(&String::from_utf8_lossy(&output.stdout), package) {
+        Some(executable) => Ok(executable),
+        None => bail!("cargo didn't report building a {package} binary"),
+    }
+}
+
+/// The `executable` of `package`'s binary in cargo's JSON build messages.
+fn built_executable(messages: &str, package: &str) -> Option<PathBuf> {
+    messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter(|message| message["target"]["name"] == package)
+        .find_map(|message| message["executable"].as_str().map(PathBuf::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_skips_member_manifests() {
+        let root = tempfile::tempdir().unwrap();
+        let day_dir = root.path().join("2024").join("17");
+        std::fs::create_dir_all(day_dir.join("src")).unwrap();
+        std::fs::write(
+            root.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"2024/17\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            day_dir.join("Cargo.toml"),
+            "[package]\nname = \"advent_2024_17\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(find_root(&day_dir.join("src")).unwrap(), root.path());
+        assert!(find_root(root.path().parent().unwrap()).is_err());
+    }
+
+    #[test]
+    fn it_finds_the_binary_in_build_messages() {
+        let messages = r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"common"},"executable":null}
+{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"advent_2024_17"},"executable":"/tmp/out/debug/advent_2024_17.exe"}
+{"reason":"build-finished","success":true}"#;
+        assert_eq!(
+            built_executable(messages, "advent_2024_17"),
+            Some(PathBuf::from("/tmp/out/debug/advent_2024_17.exe"))
+        );
+        assert_eq!(built_executable(messages, "common"), None);
+    }
+}
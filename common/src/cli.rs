@@ -1,15 +1,140 @@
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::{ArgAction, Args, CommandFactory, FromArgMatches, Parser, ValueEnum};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Cli {
-    /// Turn debugging information on
-    #[arg(short, long)]
-    pub debug: bool,
+    #[command(flatten)]
+    pub log: LogArgs,
+
+    #[command(flatten)]
+    pub viz: VizArgs,
+
+    /// Which part to run
+    #[arg(short, long, value_enum, default_value_t = Part::Both)]
+    pub part: Part,
+
+    /// Include a backtrace when a part panics
+    #[arg(long)]
+    pub backtrace: bool,
+
+    /// Read the puzzle input from this file instead of stdin
+    #[arg(short, long, conflicts_with = "example")]
+    pub input: Option<PathBuf>,
+
+    /// Read the puzzle input from `testcase_N.txt` next to the day's Cargo.toml
+    #[arg(short, long, value_name = "N")]
+    pub example: Option<u32>,
+
+    /// Fail, showing both answers, if the answer isn't this. Give it twice to check both parts
+    #[arg(long, value_name = "ANSWER")]
+    pub expect: Vec<String>,
+
+    /// Report how long reading the input, parsing and solving each part took on stderr
+    #[arg(long)]
+    pub time: bool,
+
+    /// Run each part this many times after a warm-up run and report the median. Implies --time
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    pub repeat: u16,
+}
+
+/// Command line for a binary that runs a year's days. Only days registered with it, the ones
+/// written as a [`crate::Solution`], can be run.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+pub struct YearCli {
+    /// Days to run, every registered day if none are given
+    pub days: Vec<u8>,
+
+    #[command(flatten)]
+    pub log: LogArgs,
 
     /// Which part to run
+    #[arg(short, long, value_enum, default_value_t = Part::Both)]
+    pub part: Part,
+
+    /// Include a backtrace when a part panics
+    #[arg(long)]
+    pub backtrace: bool,
+
+    /// Report how long each day's parsing and parts took on stderr
+    #[arg(long)]
+    pub time: bool,
+}
+
+/// Logging options shared by the solution binaries.
+#[derive(Args, Debug)]
+pub struct LogArgs {
+    /// Turn debugging information on, same as -v
     #[arg(short, long)]
-    pub part: u8,
+    pub debug: bool,
+
+    /// Log more: -v for debug, -vv for trace
+    #[arg(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Per-module log filter in `RUST_LOG` syntax, e.g. `info,advent_2024_16=trace`. Defaults to
+    /// `RUST_LOG`
+    #[arg(long, value_name = "FILTER")]
+    pub log: Option<String>,
+
+    /// How to lay out log lines
+    #[arg(long, value_enum, default_value_t = LogFormat::Pretty)]
+    pub log_format: LogFormat,
+
+    /// Write logs to this file instead of stderr
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+}
+
+/// Options for days that record their simulations with [`crate::viz`].
+#[derive(Args, Debug)]
+pub struct VizArgs {
+    /// Record the frames a day visualises and play them back or save them once it finishes
+    #[arg(long, value_enum, value_name = "OUTPUT")]
+    pub viz: Option<VizOutput>,
+
+    /// Where `--viz ppm|png|gif` writes its files
+    #[arg(long, value_name = "DIR", default_value = "viz")]
+    pub viz_dir: PathBuf,
+
+    /// Frames per second for `--viz terminal` and `--viz gif`
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..=100))]
+    pub viz_fps: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum VizOutput {
+    /// Play the frames back on stderr with ANSI colours
+    Terminal,
+    /// One PPM image per frame
+    Ppm,
+    /// One PNG image per frame
+    Png,
+    /// A single animated GIF
+    Gif,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Multi-line entries with source locations
+    Pretty,
+    /// One line per entry
+    Compact,
+    /// One JSON object per line
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    /// Run both parts and label their answers
+    Both,
 }
 
 impl Cli {
@@ -17,3 +142,11 @@ impl Cli {
         Self::parse()
     }
 }
+
+impl YearCli {
+    /// Parses the arguments with `about` describing the binary in its help.
+    pub fn from_args(about: String) -> Self {
+        let matches = Self::command().about(about).get_matches();
+        Self::from_arg_matches(&matches).unwrap_or_else(|error| error.exit())
+    }
+}
@@ -0,0 +1,320 @@
+//! Random puzzle inputs in the exact format of the puzzle text, for stress tests, differential
+//! tests and benchmarking parts on inputs many times the size of the real one.
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use crate::{
+    grid::coordinates::{Point, CARDINALS},
+    rng::Rng,
+};
+
+/// A `width` by `height` map walled in by `#`, with an `S` and an `E` and a fraction `walls` of the
+/// rest made of `#` (day 16). There's always a path from `S` to `E`, though usually more than one.
+pub fn grid(rng: &mut Rng, width: usize, height: usize, walls: f64) -> String {
+    assert!(
+        width >= 3 && height >= 3 && (width - 2) * (height - 2) >= 2,
+        "grid needs room for S and E inside its walls"
+    );
+    let inside =
+        |Point(x, y): Point| x > 0 && y > 0 && x < width as i64 - 1 && y < height as i64 - 1;
+    let mut cells = vec![vec!['.'; width]; height];
+    for (y, row) in cells.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            if !inside(Point(x as i64, y as i64)) || rng.chance(walls) {
+                *cell = '#';
+            }
+        }
+    }
+
+    let random_point = |rng: &mut Rng| {
+        Point(
+            rng.range(1..width as i64 - 1),
+            rng.range(1..height as i64 - 1),
+        )
+    };
+    let start = random_point(rng);
+    let mut end = random_point(rng);
+    while end == start {
+        end = random_point(rng);
+    }
+
+    // Wander from S to E, clearing the way, so the walls never cut them off.
+    let mut at = start;
+    while at != end {
+        cells[at.1 as usize][at.0 as usize] = '.';
+        let towards = Point((end.0 - at.0).signum(), (end.1 - at.1).signum());
+        let step = if rng.chance(0.6) {
+            if towards.0 != 0 && (towards.1 == 0 || rng.chance(0.5)) {
+                Point(towards.0, 0)
+            } else {
+                Point(0, towards.1)
+            }
+        } else {
+            *rng.choose(CARDINALS)
+        };
+        if inside(at + step) {
+            at = at + step;
+        }
+    }
+    cells[start.1 as usize][start.0 as usize] = 'S';
+    cells[end.1 as usize][end.0 as usize] = 'E';
+
+    cells
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// A `width` by `height` map of `#` with a single winding track from `S` to `E` through it, with no
+/// branches or dead ends off it (day 20).
+pub fn track(rng: &mut Rng, width: usize, height: usize) -> String {
+    // The track turns at the cells with odd coordinates and runs through the cells between them, so
+    // two stretches of it are always kept apart by a wall.
+    let (columns, rows) = ((width.max(1) - 1) / 2, (height.max(1) - 1) / 2);
+    assert!(
+        columns * rows >= 2,
+        "track needs room for S and E inside its walls"
+    );
+    let on_lattice = |Point(x, y): Point| x >= 0 && y >= 0 && x < columns as i64 && y < rows as i64;
+
+    // Search depth first in a random order and keep the deepest path the search reaches.
+    let first = Point(rng.below(columns) as i64, rng.below(rows) as i64);
+    let mut parents = HashMap::from([(first, first)]);
+    let mut stack = vec![first];
+    let (mut deepest, mut depth) = (first, 1);
+    while let Some(&at) = stack.last() {
+        let mut steps = CARDINALS.to_vec();
+        rng.shuffle(&mut steps);
+        let next = steps
+            .into_iter()
+            .map(|step| at + step)
+            .find(|&next| on_lattice(next) && !parents.contains_key(&next));
+        match next {
+            Some(next) => {
+                parents.insert(next, at);
+                stack.push(next);
+                if stack.len() > depth {
+                    (deepest, depth) = (next, stack.len());
+                }
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    let mut cells = vec![vec!['#'; width]; height];
+    let mut clear = |Point(x, y): Point, c| cells[y as usize][x as usize] = c;
+    let to_grid = |Point(x, y): Point| Point(2 * x + 1, 2 * y + 1);
+    let mut at = deepest;
+    clear(to_grid(at), 'E');
+    while at != first {
+        let parent = parents[&at];
+        let (from, to) = (to_grid(parent), to_grid(at));
+        clear(Point((from.0 + to.0) / 2, (from.1 + to.1) / 2), '.');
+        clear(from, if parent == first { 'S' } else { '.' });
+        at = parent;
+    }
+
+    cells
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// `count` distinct `x,y` positions in a `size` by `size` space, in the order they fall (day 18).
+pub fn falling_bytes(rng: &mut Rng, size: usize, count: usize) -> String {
+    assert!(count <= size * size, "more bytes than room for them");
+    let mut positions = (0..size)
+        .flat_map(|y| (0..size).map(move |x| (x, y)))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut positions);
+    positions
+        .into_iter()
+        .take(count)
+        .map(|(x, y)| format!("{x},{y}\n"))
+        .collect()
+}
+
+/// `count` distinct connections between computers with two-letter names, like `kh-tc` (day 23).
+/// `nodes` is how many computers there are to choose from, at most 676.
+pub fn edges(rng: &mut Rng, nodes: usize, count: usize) -> String {
+    assert!(
+        (2..=26 * 26).contains(&nodes),
+        "between 2 and 676 nodes are possible"
+    );
+    assert!(
+        count <= nodes * (nodes - 1) / 2,
+        "more edges than node pairs"
+    );
+    let mut names = (b'a'..=b'z')
+        .flat_map(|a| (b'a'..=b'z').map(move |b| format!("{}{}", a as char, b as char)))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    names.truncate(nodes);
+
+    let mut seen = HashSet::new();
+    let mut lines = String::new();
+    while seen.len() < count {
+        let (a, b) = (rng.below(nodes), rng.below(nodes));
+        if a != b && seen.insert((a.min(b), a.max(b))) {
+            lines.push_str(&format!("{}-{}\n", names[a], names[b]));
+        }
+    }
+    lines
+}
+
+/// `count` numbers from `range`, one per line.
+pub fn numbers(rng: &mut Rng, count: usize, range: Range<i64>) -> String {
+    (0..count)
+        .map(|_| format!("{}\n", rng.range(range.clone())))
+        .collect()
+}
+
+/// `rows` lines of `columns` numbers from `range`, separated by `separator`, like day 1's two
+/// columns (`"   "`) or day 2's reports (`" "`).
+pub fn number_rows(
+    rng: &mut Rng,
+    rows: usize,
+    columns: usize,
+    range: Range<i64>,
+    separator: &str,
+) -> String {
+    (0..rows)
+        .map(|_| {
+            let row = (0..columns)
+                .map(|_| rng.range(range.clone()).to_string())
+                .collect::<Vec<_>>();
+            row.join(separator) + "\n"
+        })
+        .collect()
+}
+
+/// `count` robots like `p=0,4 v=3,-3` on a `width` by `height` floor (day 14).
+pub fn robots(rng: &mut Rng, count: usize, width: usize, height: usize) -> String {
+    (0..count)
+        .map(|_| {
+            format!(
+                "p={},{} v={},{}\n",
+                rng.below(width),
+                rng.below(height),
+                rng.range(-(width as i64) + 1..width as i64),
+                rng.range(-(height as i64) + 1..height as i64),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, VecDeque};
+
+    use super::*;
+
+    #[test]
+    fn grids_are_walled_and_solvable() {
+        let mut rng = Rng::new(3);
+        for _ in 0..20 {
+            let input = grid(&mut rng, 15, 9, 0.4);
+            let rows = input.lines().collect::<Vec<_>>();
+            assert_eq!(rows.len(), 9);
+            assert!(rows.iter().all(|row| row.len() == 15));
+            assert!(rows[0].chars().chain(rows[8].chars()).all(|c| c == '#'));
+
+            let cells = rows
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.chars()
+                        .enumerate()
+                        .map(move |(x, c)| (Point(x as i64, y as i64), c))
+                })
+                .collect::<HashMap<_, _>>();
+            let find = |wanted| cells.iter().find(|(_, &c)| c == wanted).unwrap().0;
+            let (start, end) = (*find('S'), *find('E'));
+            let mut seen = HashSet::from([start]);
+            let mut queue = VecDeque::from([start]);
+            while let Some(at) = queue.pop_front() {
+                for &step in CARDINALS {
+                    let next = at + step;
+                    if cells[&next] != '#' && seen.insert(next) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+            assert!(seen.contains(&end), "no path in\n{input}");
+        }
+    }
+
+    #[test]
+    fn tracks_run_from_s_to_e_without_branching() {
+        let mut rng = Rng::new(5);
+        for (width, height) in [(15, 9), (16, 10), (5, 3)] {
+            let input = track(&mut rng, width, height);
+            let cells = input
+                .lines()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.chars()
+                        .enumerate()
+                        .map(move |(x, c)| (Point(x as i64, y as i64), c))
+                })
+                .filter(|&(_, c)| c != '#')
+                .collect::<HashMap<_, _>>();
+            let open = |point: Point| {
+                CARDINALS
+                    .iter()
+                    .filter(|&&step| cells.contains_key(&(point + step)))
+                    .count()
+            };
+            let find = |wanted| *cells.iter().find(|(_, &c)| c == wanted).unwrap().0;
+            let (start, end) = (find('S'), find('E'));
+            assert_eq!((open(start), open(end)), (1, 1), "in\n{input}");
+            assert!(cells.keys().all(|&point| open(point) <= 2), "in\n{input}");
+
+            // Following the track from S reaches E having passed every open cell.
+            let (mut previous, mut at, mut steps) = (start, start, 1);
+            while at != end {
+                let next = CARDINALS
+                    .iter()
+                    .map(|&step| at + step)
+                    .find(|next| *next != previous && cells.contains_key(next))
+                    .unwrap();
+                (previous, at, steps) = (at, next, steps + 1);
+            }
+            assert_eq!(steps, cells.len(), "in\n{input}");
+        }
+    }
+
+    #[test]
+    fn lines_match_the_puzzle_formats() {
+        let mut rng = Rng::new(7);
+        let bytes = falling_bytes(&mut rng, 7, 20);
+        assert_eq!(bytes.lines().collect::<HashSet<_>>().len(), 20);
+        assert!(bytes.lines().all(|line| {
+            let (x, y) = line.split_once(',').unwrap();
+            x.parse::<usize>().unwrap() < 7 && y.parse::<usize>().unwrap() < 7
+        }));
+
+        let connections = edges(&mut rng, 10, 30);
+        assert_eq!(connections.lines().count(), 30);
+        assert!(connections
+            .lines()
+            .all(|line| line.len() == 5 && line.as_bytes()[2] == b'-'));
+
+        let columns = number_rows(&mut rng, 4, 2, 10000..100000, "   ");
+        assert!(columns.lines().all(|line| line.len() == 13));
+
+        let robot = robots(&mut rng, 1, 11, 7);
+        let (p, v) = robot.trim_end().split_once(' ').unwrap();
+        assert!(p.starts_with("p=") && v.starts_with("v="));
+
+        assert_eq!(
+            numbers(&mut Rng::new(1), 5, 0..10),
+            numbers(&mut Rng::new(1), 5, 0..10)
+        );
+    }
+}
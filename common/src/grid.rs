@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use coordinates::Point;
+use tracing::debug;
+
+use crate::viz::{self, Cell, Colour};
 
 #[derive(Debug, Clone)]
 pub struct Grid<T> {
@@ -27,7 +30,7 @@ impl Grid<char> {
             height += 1;
         }
         let width = storage.len() / height;
-        eprintln!("Initialized grid with width: {width}, height: {height}");
+        debug!("Initialized grid with width: {width}, height: {height}");
         (
             Self {
                 width,
@@ -39,6 +42,17 @@ impl Grid<char> {
     }
 }
 
+/// The grid as it appears in puzzle text, one line per row.
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // A zero-width grid has no cells, so there are no rows to print.
+        for row in self.storage.chunks(self.width.max(1)) {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
 impl<T> Grid<T> {
     pub fn from_iterators<I, II>(input: I) -> Self
     where
@@ -52,7 +66,7 @@ impl<T> Grid<T> {
             storage.extend(row);
         }
         let width = storage.len() / height;
-        eprintln!("Initialized grid with width: {width}, height: {height}");
+        debug!("Initialized grid with width: {width}, height: {height}");
         Self {
             width,
             height,
@@ -79,6 +93,20 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Draws each cell with `cell`, as plain text through `Display` or in colour with
+    /// [`Render::coloured`], and with overlays to pick out points such as a search path.
+    pub fn render<C: Into<Cell>>(&self, cell: impl Fn(Point, &T) -> C) -> Render {
+        Render {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .points()
+                .zip(&self.storage)
+                .map(|(point, value)| cell(point, value).into())
+                .collect(),
+        }
+    }
+
     pub fn points(&self) -> impl Iterator<Item = Point> {
         coordinates::griderator(0..self.width as i64, 0..self.height as i64)
     }
@@ -94,6 +122,41 @@ impl<T> Grid<T> {
         self.storage.get((x as usize) + (self.width * (y as usize)))
     }
 
+    /// The points one step from `point` in each of `directions` (such as
+    /// [`coordinates::CARDINALS`], [`coordinates::DIAGONALS`] or [`coordinates::UNIT`]) that are
+    /// on the grid, with their values.
+    pub fn neighbours<'a>(
+        &'a self,
+        point: Point,
+        directions: &'a [Point],
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        directions.iter().filter_map(move |&direction| {
+            let neighbour = point + direction;
+            self.get(neighbour).map(|value| (neighbour, value))
+        })
+    }
+
+    /// The on-grid points north, west, south and east of `point`, with their values.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbours(point, coordinates::CARDINALS)
+    }
+
+    /// The on-grid points all around `point`, diagonals included, with their values.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbours(point, coordinates::UNIT)
+    }
+
+    /// The on-grid points north, west, south and east of `point` whose values pass `pred`, like
+    /// the open cells next to a point in a maze.
+    pub fn neighbours_where<'a>(
+        &'a self,
+        point: Point,
+        pred: impl Fn(Point, &T) -> bool + 'a,
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        self.neighbours4(point)
+            .filter(move |&(neighbour, value)| pred(neighbour, value))
+    }
+
     pub fn set<IP>(&mut self, point: IP, val: T) -> Option<&T>
     where
         IP: Into<Point>,
@@ -108,6 +171,76 @@ impl<T> Grid<T> {
     }
 }
 
+/// A grid drawn cell by cell, from [`Grid::render`].
+#[derive(Debug, Clone)]
+pub struct Render {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) cells: Vec<Cell>,
+}
+
+impl Render {
+    /// The cells row by row. A zero-width render has none.
+    pub(crate) fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    fn cell_mut(&mut self, Point(x, y): Point) -> Option<&mut Cell> {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return None;
+        }
+        self.cells.get_mut(x as usize + self.width * y as usize)
+    }
+
+    /// Colours the cells at `points`, keeping their glyphs. Points off the grid are ignored.
+    pub fn highlight<'p>(
+        mut self,
+        points: impl IntoIterator<Item = &'p Point>,
+        colour: Colour,
+    ) -> Self {
+        for &point in points {
+            if let Some(cell) = self.cell_mut(point) {
+                cell.colour = colour;
+            }
+        }
+        self
+    }
+
+    /// Draws `glyph` in `colour` over the cells at `points`, like a path through a maze.
+    pub fn overlay<'p>(
+        mut self,
+        points: impl IntoIterator<Item = &'p Point>,
+        glyph: char,
+        colour: Colour,
+    ) -> Self {
+        for &point in points {
+            if let Some(cell) = self.cell_mut(point) {
+                *cell = Cell::new(glyph, colour);
+            }
+        }
+        self
+    }
+
+    /// The grid with ANSI colour codes, for printing to a terminal.
+    pub fn coloured(&self) -> String {
+        self.rows().map(|row| viz::ansi_line(row) + "\n").collect()
+    }
+}
+
+/// Just the glyphs, for comparing against expected text in tests.
+impl fmt::Display for Render {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(
+                f,
+                "{}",
+                row.iter().map(|cell| cell.glyph).collect::<String>()
+            )?;
+        }
+        Ok(())
+    }
+}
+
 pub mod coordinates {
     use std::ops::{Add, Mul};
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -164,6 +297,7 @@ pub mod coordinates {
         NORTH, NORTH_WEST, WEST, SOUTH_WEST, SOUTH, SOUTH_EAST, EAST, NORTH_EAST,
     ];
     pub const CARDINALS: &[Point] = &[NORTH, WEST, SOUTH, EAST];
+    pub const DIAGONALS: &[Point] = &[NORTH_WEST, SOUTH_WEST, SOUTH_EAST, NORTH_EAST];
 
     pub fn griderator<I>(range_x: I, range_y: I) -> impl Iterator<Item = Point>
     where
@@ -172,3 +306,52 @@ pub mod coordinates {
         range_y.flat_map(move |y| range_x.clone().map(move |x| Point(x, y)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FromPuzzleInput;
+
+    #[test]
+    fn it_renders_with_overlays() {
+        let maze = "#####\n#S..#\n#.#E#\n#####\n";
+        let grid = Grid::<char>::from_puzzle_input(maze).unwrap();
+        assert_eq!(grid.to_string(), maze);
+
+        let path = [Point(1, 1), Point(2, 1), Point(3, 1), Point(3, 2)];
+        let render = grid
+            .render(|_, &c| c)
+            .overlay(&path[1..3], 'o', Colour::RED)
+            .highlight(&[Point(3, 2), Point(9, 9)], Colour::GREEN);
+        assert_eq!(render.to_string(), "#####\n#Soo#\n#.#E#\n#####\n");
+        assert!(render.coloured().contains("\x1b[38;2;220;50;47mo"));
+        assert!(render.coloured().contains("\x1b[38;2;133;153;0mE"));
+    }
+
+    #[test]
+    fn it_prints_nothing_for_a_zero_width_grid() {
+        let grid = Grid::<char>::from_rows(Vec::new());
+        assert_eq!(grid.to_string(), "");
+        let render = grid.render(|_, &c| c);
+        assert_eq!(render.to_string(), "");
+        assert_eq!(render.coloured(), "");
+    }
+
+    #[test]
+    fn it_finds_neighbours_on_the_grid() {
+        let grid = Grid::<char>::from_puzzle_input("ab\ncd\n").unwrap();
+        let corner = grid.neighbours4(Point(0, 0)).collect::<Vec<_>>();
+        assert_eq!(corner, [(Point(0, 1), &'c'), (Point(1, 0), &'b')]);
+        assert_eq!(grid.neighbours8(Point(0, 0)).count(), 3);
+        assert_eq!(
+            grid.neighbours(Point(1, 1), coordinates::DIAGONALS)
+                .collect::<Vec<_>>(),
+            [(Point(0, 0), &'a')]
+        );
+        assert_eq!(
+            grid.neighbours_where(Point(1, 1), |_, &c| c != 'b')
+                .collect::<Vec<_>>(),
+            [(Point(0, 1), &'c')]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+use std::{
+    cell::RefCell,
+    io::{IsTerminal, Read},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+
+use crate::{cli::Cli, grid::Grid};
+
+/// Types a part function can take its puzzle input as. The input is parsed once before the part
+/// runs, so days don't each have to rebuild a `Grid` or split lines themselves.
+///
+/// Parts can also borrow the raw input as `&str` or `&[u8]`, see [`PuzzleFn`].
+pub trait FromPuzzleInput: Sized {
+    fn from_puzzle_input(input: &str) -> anyhow::Result<Self>;
+}
+
+impl FromPuzzleInput for String {
+    fn from_puzzle_input(input: &str) -> anyhow::Result<Self> {
+        Ok(input.to_string())
+    }
+}
+
+impl FromPuzzleInput for Vec<u8> {
+    fn from_puzzle_input(input: &str) -> anyhow::Result<Self> {
+        Ok(input.as_bytes().to_vec())
+    }
+}
+
+/// One entry per line, without line endings.
+impl FromPuzzleInput for Vec<String> {
+    fn from_puzzle_input(input: &str) -> anyhow::Result<Self> {
+        Ok(input.lines().map(String::from).collect())
+    }
+}
+
+/// Every number in the input, whether they're separated by whitespace or commas.
+impl FromPuzzleInput for Vec<i64> {
+    fn from_puzzle_input(input: &str) -> anyhow::Result<Self> {
+        input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|n| !n.is_empty())
+            .map(|n| {
+                n.parse()
+                    .map_err(|e| anyhow::anyhow!("Invalid number {n:?} in input: {e}"))
+            })
+            .collect()
+    }
+}
+
+/// One row per line.
+impl FromPuzzleInput for Grid<char> {
+    fn from_puzzle_input(input: &str) -> anyhow::Result<Self> {
+        grid_rows(input.lines().map(|line| line.chars().collect()).collect())
+    }
+}
+
+/// One row per line, holding the raw bytes rather than their digit values.
+impl FromPuzzleInput for Grid<u8> {
+    fn from_puzzle_input(input: &str) -> anyhow::Result<Self> {
+        grid_rows(input.lines().map(|line| line.bytes().collect()).collect())
+    }
+}
+
+/// A grid of `rows`, which must all be as long as the first, non-empty one.
+fn grid_rows<T>(rows: Vec<Vec<T>>) -> anyhow::Result<Grid<T>> {
+    let Some(width) = rows.first().map(Vec::len).filter(|&width| width > 0) else {
+        bail!("Can't make a grid from empty input");
+    };
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+        bail!(
+            "Line {} of the grid is {} long, but line 1 is {width}",
+            i + 1,
+            row.len()
+        );
+    }
+    Ok(Grid::from_rows(rows))
+}
+
+/// A part (or shared parse step) that can be run on the raw input `&'a str`, whether it takes an
+/// owned [`FromPuzzleInput`] type or borrows the input as `&str` or `&[u8]`. `M` is one of
+/// [`Parsed`], [`Str`] or [`Bytes`] and only keeps the implementations apart; it's inferred.
+pub trait PuzzleFn<'a, M, O> {
+    /// What the function takes, made from the raw input by [`PuzzleFn::parse`].
+    type Input;
+
+    fn parse(&self, input: &'a str) -> anyhow::Result<Self::Input>;
+    fn call(&self, input: Self::Input) -> anyhow::Result<O>;
+}
+
+/// Marks a [`PuzzleFn`] taking a [`FromPuzzleInput`] type `I`.
+pub struct Parsed<I>(PhantomData<I>);
+/// Marks a [`PuzzleFn`] taking `&str`.
+pub struct Str;
+/// Marks a [`PuzzleFn`] taking `&[u8]`.
+pub struct Bytes;
+
+impl<'a, F, I, O> PuzzleFn<'a, Parsed<I>, O> for F
+where
+    F: Fn(I) -> anyhow::Result<O>,
+    I: FromPuzzleInput,
+{
+    type Input = I;
+
+    fn parse(&self, input: &'a str) -> anyhow::Result<I> {
+        I::from_puzzle_input(input)
+    }
+
+    fn call(&self, input: I) -> anyhow::Result<O> {
+        self(input)
+    }
+}
+
+impl<'a, F, O> PuzzleFn<'a, Str, O> for F
+where
+    F: Fn(&'a str) -> anyhow::Result<O>,
+{
+    type Input = &'a str;
+
+    fn parse(&self, input: &'a str) -> anyhow::Result<&'a str> {
+        Ok(input)
+    }
+
+    fn call(&self, input: &'a str) -> anyhow::Result<O> {
+        self(input)
+    }
+}
+
+impl<'a, F, O> PuzzleFn<'a, Bytes, O> for F
+where
+    F: Fn(&'a [u8]) -> anyhow::Result<O>,
+{
+    type Input = &'a [u8];
+
+    fn parse(&self, input: &'a str) -> anyhow::Result<&'a [u8]> {
+        Ok(input.as_bytes())
+    }
+
+    fn call(&self, input: &'a [u8]) -> anyhow::Result<O> {
+        self(input)
+    }
+}
+
+thread_local! {
+    static PARSING_LINE: RefCell<Option<(usize, String)>> = const { RefCell::new(None) };
+}
+
+/// Parses each line with `f`, keeping track of which line it's on so that if `f` panics, say on an
+/// `unwrap()`, the error shows the offending line.
+pub fn parse_lines<L, T>(lines: impl IntoIterator<Item = L>, mut f: impl FnMut(&str) -> T) -> Vec<T>
+where
+    L: AsRef<str>,
+{
+    let parsed = lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.as_ref();
+            PARSING_LINE.set(Some((i + 1, line.to_string())));
+            f(line)
+        })
+        .collect();
+    PARSING_LINE.set(None);
+    parsed
+}
+
+/// The line number and contents [`parse_lines`] was on when it was interrupted by a panic.
+pub(crate) fn take_parsing_line() -> Option<(usize, String)> {
+    PARSING_LINE.take()
+}
+
+/// Reads the raw puzzle input from `--input`, `--example`, or stdin. When stdin is a terminal
+/// nobody is going to paste an input into it, so the input cached by `cargo advent` is used
+/// instead.
+pub fn read(cli: &Cli) -> anyhow::Result<String> {
+    let path = if let Some(path) = &cli.input {
+        path.clone()
+    } else if let Some(n) = cli.example {
+        manifest_dir()?.join(format!("testcase_{n}.txt"))
+    } else if std::io::stdin().is_terminal() {
+        let (year, day) = running_day()?;
+        let path = cached_input_path(year, day)?;
+        if !path.exists() {
+            bail!(
+                "stdin is a terminal and there's no cached input at {}. Run `cargo advent run` \
+                 once to download it, or pass --input or --example",
+                path.display()
+            );
+        }
+        path
+    } else {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        return Ok(input);
+    };
+    std::fs::read_to_string(&path).with_context(|| format!("Couldn't read {}", path.display()))
+}
+
+/// The running day's crate directory. `cargo run` says where it is, otherwise assume we were
+/// started from it.
+fn manifest_dir() -> anyhow::Result<PathBuf> {
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(std::env::current_dir()?),
+    }
+}
+
+/// Reads the input `cargo advent` cached for a day.
+pub(crate) fn read_cached(year: i32, day: u8) -> anyhow::Result<String> {
+    let path = cached_input_path(year, day)?;
+    std::fs::read_to_string(&path).with_context(|| {
+        format!(
+            "No cached input at {}. Run `cargo advent run` for the day to download it",
+            path.display()
+        )
+    })
+}
+
+/// The year and day of the running day's crate, from it being at `<year>/<day>`.
+fn running_day() -> anyhow::Result<(i32, u8)> {
+    let day_dir = manifest_dir()?;
+    let year = day_dir
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str()?.parse().ok());
+    let day = day_dir
+        .file_name()
+        .and_then(|name| name.to_str()?.parse().ok());
+    let (Some(year), Some(day)) = (year, day) else {
+        bail!(
+            "Can't tell which day {} is, pass --input or --example",
+            day_dir.display()
+        );
+    };
+    Ok((year, day))
+}
+
+/// Where `cargo advent` caches the input for a day, honouring `cache_dir` in the workspace's
+/// advent.toml.
+fn cached_input_path(year: i32, day: u8) -> anyhow::Result<PathBuf> {
+    let ws_root = workspace_root(&manifest_dir()?)?;
+    let cache_dir = std::fs::read_to_string(ws_root.join("advent.toml"))
+        .ok()
+        .and_then(|config| config.parse::<toml::Table>().ok())
+        .and_then(|config| Some(config.get("cache_dir")?.as_str()?.to_string()))
+        .unwrap_or_else(|| String::from(".cache"));
+    Ok(ws_root
+        .join(cache_dir)
+        .join(format!("input_{year}_{day}.txt")))
+}
+
+/// The workspace containing `start`: the first `Cargo.toml` above it that has a `workspace`
+/// table, found the same way `cargo advent` finds it.
+fn workspace_root(start: &Path) -> anyhow::Result<PathBuf> {
+    for dir in start.ancestors() {
+        let manifest_path = dir.join("Cargo.toml");
+        if !manifest_path.is_file() {
+            continue;
+        }
+        let manifest: toml::Table = std::fs::read_to_string(&manifest_path)?
+            .parse()
+            .with_context(|| format!("Couldn't parse {}", manifest_path.display()))?;
+        if manifest.contains_key("workspace") {
+            return Ok(dir.to_path_buf());
+        }
+    }
+    bail!("{} isn't in a cargo workspace", start.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_numbers_and_grids() {
+        let numbers = Vec::<i64>::from_puzzle_input("3   4\n-4,3\n").unwrap();
+        assert_eq!(numbers, vec![3, 4, -4, 3]);
+        assert!(Vec::<i64>::from_puzzle_input("3 x\n").is_err());
+
+        let grid = Grid::<char>::from_puzzle_input("#.\n.S\n").unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+        assert_eq!(grid.get((1, 1)), Some(&'S'));
+
+        let grid = Grid::<u8>::from_puzzle_input("12\n34\n").unwrap();
+        assert_eq!(grid.get((0, 1)), Some(&b'3'));
+    }
+
+    #[test]
+    fn it_finds_the_workspace_by_its_manifest() {
+        let ws = tempfile::tempdir().unwrap();
+        let day = ws.path().join("2024/1");
+        std::fs::create_dir_all(&day).unwrap();
+        std::fs::write(
+            day.join("Cargo.toml"),
+            "[package]\nname = \"day\" # not a [workspace]\n",
+        )
+        .unwrap();
+        assert!(workspace_root(&day).is_err());
+
+        std::fs::write(
+            ws.path().join("Cargo.toml"),
+            "[workspace.package]\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        assert_eq!(workspace_root(&day).unwrap(), ws.path());
+    }
+
+    #[test]
+    fn it_rejects_empty_and_ragged_grids() {
+        assert!(Grid::<char>::from_puzzle_input("").is_err());
+        assert!(Grid::<u8>::from_puzzle_input("\n").is_err());
+        let error = Grid::<char>::from_puzzle_input("#.\n.\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 2 of the grid is 1 long, but line 1 is 2"
+        );
+        assert!(Grid::<u8>::from_puzzle_input("12\n345\n").is_err());
+    }
+
+    #[test]
+    fn it_splits_lines_without_endings() {
+        let lines = Vec::<String>::from_puzzle_input("a\r\nb\n\nc\n").unwrap();
+        assert_eq!(lines, vec!["a", "b", "", "c"]);
+    }
+}
@@ -1,35 +1,325 @@
-use tracing_subscriber::filter::LevelFilter;
+use std::{fs::File, sync::Mutex};
+
+use anyhow::{bail, Context};
+use tracing::debug;
+use tracing_subscriber::{
+    filter::{EnvFilter, LevelFilter},
+    fmt::writer::BoxMakeWriter,
+};
 
 pub mod cli;
+pub mod gen;
 pub mod grid;
+pub mod input;
 pub mod iterators;
+pub mod memory;
+mod panics;
+pub mod rng;
 pub mod search;
+pub mod solution;
+pub mod testing;
+pub mod timing;
+pub mod viz;
+
+pub use input::{FromPuzzleInput, PuzzleFn};
+pub use solution::{Registry, Solution};
+
+use cli::Part;
+use timing::Timer;
+
+/// Reads the puzzle input and runs the part chosen on the command line. Each part can
+/// take its input as any [`FromPuzzleInput`] type, or borrow it as `&str` or `&[u8]`.
+pub fn advent<P1, M1, O1, P2, M2, O2>(part1: P1, part2: P2) -> anyhow::Result<String>
+where
+    P1: for<'a> PuzzleFn<'a, M1, O1>,
+    O1: ToString,
+    P2: for<'a> PuzzleFn<'a, M2, O2>,
+    O2: ToString,
+{
+    let (cli_args, input, mut timer) = init()?;
+    let answer = run_separately(
+        &input,
+        cli_args.part,
+        &cli_args.expect,
+        &mut timer,
+        part1,
+        part2,
+    );
+    report_timings(&cli_args, &timer);
+    viz::finish()?;
+    answer
+}
+
+/// Like [`advent`], but with a `parse` step whose result both parts borrow, so expensive setup
+/// only happens once when running both.
+pub fn advent_with_parse<P, M, S, P1, P2, O1, O2>(
+    parse: P,
+    part1: P1,
+    part2: P2,
+) -> anyhow::Result<String>
+where
+    P: for<'a> PuzzleFn<'a, M, S>,
+    P1: Fn(&S) -> anyhow::Result<O1>,
+    O1: ToString,
+    P2: Fn(&S) -> anyhow::Result<O2>,
+    O2: ToString,
+{
+    let (cli_args, input, mut timer) = init()?;
+    let answer = run_with_parse(
+        &input,
+        cli_args.part,
+        &cli_args.expect,
+        &mut timer,
+        parse,
+        part1,
+        part2,
+    );
+    report_timings(&cli_args, &timer);
+    viz::finish()?;
+    answer
+}
+
+/// Reads the puzzle input and runs the part chosen on the command line, for the `main` of a day
+/// written as a [`Solution`].
+pub fn solve<S: Solution>() -> anyhow::Result<String> {
+    let (cli_args, input, mut timer) = init()?;
+    let answer = solution::run::<S>(&input, cli_args.part, &cli_args.expect, &mut timer);
+    report_timings(&cli_args, &timer);
+    viz::finish()?;
+    answer
+}
+
+/// Parses `input` separately for each part, as [`advent`] does.
+fn run_separately<'a, P1, M1, O1, P2, M2, O2>(
+    input: &'a str,
+    part: Part,
+    expected: &[String],
+    timer: &mut Timer,
+    part1: P1,
+    part2: P2,
+) -> anyhow::Result<String>
+where
+    P1: PuzzleFn<'a, M1, O1>,
+    O1: ToString,
+    P2: PuzzleFn<'a, M2, O2>,
+    O2: ToString,
+{
+    run_parts(
+        part,
+        expected,
+        timer,
+        |timer| {
+            timer.repeat(|timer| {
+                let parsed = timer.time("part 1 parse", || {
+                    panics::catch("Parsing", || part1.parse(input))
+                })?;
+                timer.time("part 1 solve", || {
+                    panics::catch("Part 1", || part1.call(parsed))
+                })
+            })
+        },
+        |timer| {
+            timer.repeat(|timer| {
+                let parsed = timer.time("part 2 parse", || {
+                    panics::catch("Parsing", || part2.parse(input))
+                })?;
+                timer.time("part 2 solve", || {
+                    panics::catch("Part 2", || part2.call(parsed))
+                })
+            })
+        },
+    )
+}
 
-pub fn advent<P1, P2, O1, O2>(part1: P1, part2: P2) -> anyhow::Result<String>
+fn run_with_parse<'a, P, M, S, P1, P2, O1, O2>(
+    input: &'a str,
+    part: Part,
+    expected: &[String],
+    timer: &mut Timer,
+    parse: P,
+    part1: P1,
+    part2: P2,
+) -> anyhow::Result<String>
 where
-    P1: Fn(Vec<String>) -> anyhow::Result<O1>,
+    P: PuzzleFn<'a, M, S>,
+    P1: Fn(&S) -> anyhow::Result<O1>,
     O1: ToString,
-    P2: Fn(Vec<String>) -> anyhow::Result<O2>,
+    P2: Fn(&S) -> anyhow::Result<O2>,
     O2: ToString,
 {
+    let parsed = timer.repeat(|timer| {
+        let parsed = timer.time("parse", || panics::catch("Parsing", || parse.parse(input)))?;
+        timer.time("shared parse", || {
+            panics::catch("Parsing", || parse.call(parsed))
+        })
+    })?;
+    run_parts(
+        part,
+        expected,
+        timer,
+        |timer| {
+            timer.repeat(|timer| {
+                timer.time("part 1 solve", || {
+                    panics::catch("Part 1", || part1(&parsed))
+                })
+            })
+        },
+        |timer| {
+            timer.repeat(|timer| {
+                timer.time("part 2 solve", || {
+                    panics::catch("Part 2", || part2(&parsed))
+                })
+            })
+        },
+    )
+}
+
+/// Parses the command line, sets up logging and reads the puzzle input.
+fn init() -> anyhow::Result<(cli::Cli, String, Timer)> {
     let cli_args = cli::Cli::from_args();
+    init_tracing(&cli_args.log)?;
+    panics::install_hook(cli_args.backtrace);
+    viz::configure(&cli_args.viz);
 
-    let level_filter = if cli_args.debug {
-        LevelFilter::DEBUG
-    } else {
-        LevelFilter::INFO
+    let mut timer = Timer::new(cli_args.repeat.into());
+    let input = timer.time("read input", || input::read(&cli_args))?;
+    Ok((cli_args, input, timer))
+}
+
+/// Sets up logging from `-v`, `--log` (or `RUST_LOG`), `--log-format` and `--log-file`.
+fn init_tracing(args: &cli::LogArgs) -> anyhow::Result<()> {
+    let level = match args.verbose.saturating_add(u8::from(args.debug)) {
+        0 => LevelFilter::INFO,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let directives = match &args.log {
+        Some(filter) => filter.clone(),
+        None => std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_default(),
     };
+    let filter = EnvFilter::builder()
+        .with_default_directive(level.into())
+        .parse(directives)
+        .context("Invalid log filter")?;
 
-    tracing_subscriber::fmt()
-        .pretty()
-        .with_max_level(level_filter)
-        .with_writer(std::io::stderr)
-        .init();
-
-    let input = std::io::stdin().lines().map(Result::unwrap).collect();
-    Ok(match cli_args.part {
-        1 => part1(input)?.to_string(),
-        2 => part2(input)?.to_string(),
-        p => anyhow::bail!("Invalid argument: --part {p}"),
+    let (writer, ansi) = match &args.log_file {
+        Some(path) => {
+            let file = File::create(path)
+                .with_context(|| format!("Couldn't create log file {}", path.display()))?;
+            (BoxMakeWriter::new(Mutex::new(file)), false)
+        }
+        None => (BoxMakeWriter::new(std::io::stderr), true),
+    };
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(ansi);
+    match args.log_format {
+        cli::LogFormat::Pretty => subscriber.pretty().init(),
+        cli::LogFormat::Compact => subscriber.compact().init(),
+        cli::LogFormat::Json => subscriber.json().init(),
+    }
+    Ok(())
+}
+
+/// Timings go to stderr so they never end up in the answer.
+fn report_timings(cli_args: &cli::Cli, timer: &Timer) {
+    if cli_args.time || cli_args.repeat > 1 {
+        eprint!("{}", timer.report());
+    } else {
+        debug!("Timings:\n{}", timer.report());
+    }
+}
+
+/// Runs the selected part, or both with each answer labelled, checking each answer against the
+/// `--expect`ed one if there is one.
+fn run_parts<P1, P2, O1, O2>(
+    part: Part,
+    expected: &[String],
+    timer: &mut Timer,
+    part1: P1,
+    part2: P2,
+) -> anyhow::Result<String>
+where
+    P1: FnOnce(&mut Timer) -> anyhow::Result<O1>,
+    O1: ToString,
+    P2: FnOnce(&mut Timer) -> anyhow::Result<O2>,
+    O2: ToString,
+{
+    let parts_run = if part == Part::Both { 2 } else { 1 };
+    if expected.len() > parts_run {
+        bail!(
+            "--expect was given {} times, but only {parts_run} part(s) are being run",
+            expected.len()
+        );
+    }
+    let check = |label: &str, answer: String, expected: Option<&String>| match expected {
+        Some(expected) if *expected != answer => {
+            bail!("{label} answer doesn't match --expect\n- {expected}\n+ {answer}")
+        }
+        _ => Ok(answer),
+    };
+    Ok(match part {
+        Part::One => check("Part 1", part1(timer)?.to_string(), expected.first())?,
+        Part::Two => check("Part 2", part2(timer)?.to_string(), expected.first())?,
+        Part::Both => {
+            let answer1 = check("Part 1", part1(timer)?.to_string(), expected.first())?;
+            let answer2 = check("Part 2", part2(timer)?.to_string(), expected.get(1))?;
+            format!("Part 1: {answer1}\nPart 2: {answer2}")
+        }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_answers_are_checked() {
+        let run = |part, expected: &[&str]| {
+            let expected = expected.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+            run_parts(
+                part,
+                &expected,
+                &mut Timer::new(1),
+                |_| Ok(42),
+                |_| Ok("abc"),
+            )
+        };
+        assert_eq!(run(Part::Two, &["abc"]).unwrap(), "abc");
+        assert_eq!(
+            run(Part::Both, &["42", "abc"]).unwrap(),
+            "Part 1: 42\nPart 2: abc"
+        );
+        assert_eq!(
+            run(Part::Both, &["42", "abd"]).unwrap_err().to_string(),
+            "Part 2 answer doesn't match --expect\n- abd\n+ abc"
+        );
+        assert!(run(Part::One, &["42", "abc"]).is_err());
+    }
+
+    #[test]
+    fn parts_can_borrow_the_input() {
+        let input = String::from("ab\ncd\n");
+        let answer = run_separately(
+            &input,
+            Part::Both,
+            &[],
+            &mut Timer::new(1),
+            |input: &str| Ok(input.lines().count()),
+            |input: &[u8]| Ok(input.len()),
+        );
+        assert_eq!(answer.unwrap(), "Part 1: 2\nPart 2: 6");
+
+        let answer = run_separately(
+            &input,
+            Part::One,
+            &[],
+            &mut Timer::new(1),
+            |lines: Vec<String>| Ok(lines.concat()),
+            |_: String| Ok(0),
+        );
+        assert_eq!(answer.unwrap(), "abcd");
+    }
+}
@@ -0,0 +1,141 @@
+//! Heap usage statistics from a counting global allocator, enabled with the `alloc-stats` feature.
+//! Without it [`measure`] just runs the closure and reports nothing.
+
+/// What a measured closure allocated. `peak` is the most it had allocated at once on top of
+/// whatever was already allocated when it started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub peak: usize,
+    pub allocations: usize,
+    pub bytes: usize,
+}
+
+impl std::fmt::Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocations, {} allocated",
+            format_bytes(self.peak),
+            self.allocations,
+            format_bytes(self.bytes)
+        )
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "alloc-stats")]
+pub use counting::measure;
+
+#[cfg(not(feature = "alloc-stats"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    (f(), None)
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    };
+
+    use super::Usage;
+
+    struct CountingAllocator;
+
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        BYTES.fetch_add(size, Relaxed);
+        let current = CURRENT.fetch_add(size, Relaxed) + size;
+        PEAK.fetch_max(current, Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                CURRENT.fetch_sub(layout.size(), Relaxed);
+                allocated(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    /// Runs `f` and reports what it allocated.
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+        let start_current = CURRENT.load(Relaxed);
+        let start_allocations = ALLOCATIONS.load(Relaxed);
+        let start_bytes = BYTES.load(Relaxed);
+        PEAK.store(start_current, Relaxed);
+        let result = f();
+        let usage = Usage {
+            peak: PEAK.load(Relaxed).saturating_sub(start_current),
+            allocations: ALLOCATIONS.load(Relaxed) - start_allocations,
+            bytes: BYTES.load(Relaxed) - start_bytes,
+        };
+        (result, Some(usage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_formats_sizes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn it_counts_allocations() {
+        let (_, usage) = measure(|| vec![0u8; 4096]);
+        let usage = usage.unwrap();
+        assert!(usage.allocations >= 1);
+        assert!(usage.bytes >= 4096);
+        assert!(usage.peak >= 4096);
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    backtrace::Backtrace,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+};
+
+use anyhow::anyhow;
+
+use crate::input;
+
+static CAPTURE_BACKTRACE: AtomicBool = AtomicBool::new(false);
+static HOOK: Once = Once::new();
+
+thread_local! {
+    /// Whether this thread is inside [`catch`], which reports panics itself.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// What the panic hook saw of the last panic on this thread, for [`catch`] to report.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Replaces the default panic output inside [`catch`] with a hook that keeps the message, so a
+/// panicking part is reported like any other error. With `backtrace` the error also carries a
+/// backtrace of where the panic happened. [`catch`] installs the hook itself if it has to.
+pub(crate) fn install_hook(backtrace: bool) {
+    CAPTURE_BACKTRACE.store(backtrace, Ordering::Relaxed);
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                return default_hook(info);
+            }
+            let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+            let mut details = match info.location() {
+                Some(location) => format!("{message}, at {location}"),
+                None => message.to_string(),
+            };
+            if CAPTURE_BACKTRACE.load(Ordering::Relaxed) {
+                details.push_str(&format!("\n\nBacktrace:\n{}", Backtrace::force_capture()));
+            }
+            LAST_PANIC.set(Some(details));
+        }));
+    });
+}
+
+/// Runs `f`, turning a panic into an error naming `stage` and, if it happened inside
+/// [`input::parse_lines`], the line being parsed.
+pub(crate) fn catch<T>(stage: &str, f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    install_hook(CAPTURE_BACKTRACE.load(Ordering::Relaxed));
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+    match result {
+        Ok(result) => result,
+        Err(_) => {
+            let details = LAST_PANIC
+                .take()
+                .unwrap_or_else(|| String::from("no details"));
+            let error = anyhow!("{stage} panicked: {details}");
+            Err(match input::take_parsing_line() {
+                Some((number, line)) => {
+                    error.context(format!("While parsing line {number}: {line:?}"))
+                }
+                None => error,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_while_parsing_name_the_line() {
+        install_hook(false);
+        let error = catch("part 1", || {
+            Ok(input::parse_lines("1\n2\nthree\n".lines(), |line| {
+                line.parse::<i64>().unwrap()
+            }))
+        })
+        .unwrap_err();
+        assert_eq!(error.to_string(), "While parsing line 3: \"three\"");
+        assert!(format!("{error:#}").contains("part 1 panicked: called `Result::unwrap()`"));
+
+        let error = catch("part 2", || -> anyhow::Result<()> { panic!("oops") }).unwrap_err();
+        assert!(error.to_string().starts_with("part 2 panicked: oops, at "));
+    }
+}
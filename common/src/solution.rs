@@ -0,0 +1,221 @@
+use std::{marker::PhantomData, time::Instant};
+
+use anyhow::bail;
+
+use crate::{cli, input, timing::Timer};
+
+/// A day's puzzle, so it can be run by its own binary through [`crate::solve`] or alongside the
+/// rest of the year's days through a [`Registry`].
+pub trait Solution {
+    const YEAR: i32;
+    const DAY: u8;
+
+    /// Whatever both parts work from, built once from the raw puzzle input.
+    type Parsed;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<impl ToString>;
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<impl ToString>;
+}
+
+/// Parses `input` once and runs the selected parts of `S` on it, checking any expected answers.
+pub(crate) fn run<S: Solution>(
+    input: &str,
+    part: cli::Part,
+    expected: &[String],
+    timer: &mut Timer,
+) -> anyhow::Result<String> {
+    crate::run_with_parse(
+        input,
+        part,
+        expected,
+        timer,
+        S::parse,
+        |parsed: &S::Parsed| S::part1(parsed).map(|answer| answer.to_string()),
+        |parsed: &S::Parsed| S::part2(parsed).map(|answer| answer.to_string()),
+    )
+}
+
+/// A [`Solution`] with its types erased, so different days can share a [`Registry`].
+trait Entry {
+    fn year(&self) -> i32;
+    fn day(&self) -> u8;
+    fn run(&self, input: &str, part: cli::Part, timer: &mut Timer) -> anyhow::Result<String>;
+}
+
+struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> Entry for Registered<S> {
+    fn year(&self) -> i32 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, input: &str, part: cli::Part, timer: &mut Timer) -> anyhow::Result<String> {
+        run::<S>(input, part, &[], timer)
+    }
+}
+
+/// The solutions an all-days binary can run, in one process and from one build.
+#[derive(Default)]
+pub struct Registry {
+    entries: Vec<Box<dyn Entry>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<S: Solution + 'static>(mut self) -> Self {
+        self.entries.push(Box::new(Registered::<S>(PhantomData)));
+        self.entries
+            .sort_by_key(|entry| (entry.year(), entry.day()));
+        self
+    }
+
+    /// The `(year, day)` of every registered solution, in order.
+    pub fn days(&self) -> Vec<(i32, u8)> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.year(), entry.day()))
+            .collect()
+    }
+
+    /// Runs `part` of the given day's solution on `input`.
+    pub fn run(
+        &self,
+        year: i32,
+        day: u8,
+        input: &str,
+        part: cli::Part,
+        timer: &mut Timer,
+    ) -> anyhow::Result<String> {
+        let Some(entry) = self
+            .entries
+            .iter()
+            .find(|entry| entry.year() == year && entry.day() == day)
+        else {
+            bail!("No solution registered for {year} day {day}");
+        };
+        entry.run(input, part, timer)
+    }
+
+    /// Entry point for a year binary: runs the days named on the command line, or every
+    /// registered day, on their cached inputs. A failing day is reported and the rest still run.
+    pub fn main(&self) -> anyhow::Result<()> {
+        let registered = self
+            .days()
+            .iter()
+            .map(|(_, day)| day.to_string())
+            .collect::<Vec<_>>();
+        let cli_args = cli::YearCli::from_args(format!(
+            "Runs the days written as a common::Solution and registered with this binary: {}",
+            registered.join(", ")
+        ));
+        crate::init_tracing(&cli_args.log)?;
+        crate::panics::install_hook(cli_args.backtrace);
+
+        let selected = self
+            .days()
+            .into_iter()
+            .filter(|(_, day)| cli_args.days.is_empty() || cli_args.days.contains(day))
+            .collect::<Vec<_>>();
+        if let Some(missing) = cli_args
+            .days
+            .iter()
+            .find(|&&day| !selected.iter().any(|&(_, d)| d == day))
+        {
+            bail!("No solution registered for day {missing}");
+        }
+
+        let start = Instant::now();
+        let mut failed = 0;
+        for (year, day) in selected {
+            println!("Day {day}");
+            let mut timer = Timer::new(1);
+            let answer = input::read_cached(year, day)
+                .and_then(|input| self.run(year, day, &input, cli_args.part, &mut timer));
+            match answer {
+                Ok(answer) => println!("{answer}"),
+                Err(error) => {
+                    failed += 1;
+                    eprintln!("Error: {error:#}");
+                }
+            }
+            if cli_args.time {
+                eprint!("{}", timer.report());
+            }
+        }
+        if cli_args.time {
+            eprintln!("total  {:?}", start.elapsed());
+        }
+        if failed > 0 {
+            bail!("{failed} day(s) failed");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: i32 = 2024;
+        const DAY: u8 = 2;
+
+        type Parsed = Vec<i64>;
+
+        fn parse(input: &str) -> anyhow::Result<Vec<i64>> {
+            Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(numbers: &Vec<i64>) -> anyhow::Result<impl ToString> {
+            Ok(numbers.iter().sum::<i64>())
+        }
+
+        fn part2(numbers: &Vec<i64>) -> anyhow::Result<impl ToString> {
+            Ok(numbers.iter().product::<i64>())
+        }
+    }
+
+    struct Empty;
+
+    impl Solution for Empty {
+        const YEAR: i32 = 2024;
+        const DAY: u8 = 1;
+
+        type Parsed = ();
+
+        fn parse(_: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> anyhow::Result<impl ToString> {
+            Ok("")
+        }
+
+        fn part2(_: &()) -> anyhow::Result<impl ToString> {
+            Ok("")
+        }
+    }
+
+    #[test]
+    fn registry_runs_days_in_order() {
+        let registry = Registry::new().register::<Sum>().register::<Empty>();
+        assert_eq!(registry.days(), [(2024, 1), (2024, 2)]);
+
+        let mut timer = Timer::new(1);
+        let answer = registry.run(2024, 2, "2\n3\n4\n", cli::Part::Both, &mut timer);
+        assert_eq!(answer.unwrap(), "Part 1: 9\nPart 2: 24");
+        assert!(registry
+            .run(2024, 3, "", cli::Part::One, &mut timer)
+            .is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! Differential testing: run several implementations of the same part on random inputs and check
+//! they all agree, so a rewrite for speed can be checked against the simple version. When they
+//! don't, the input is shrunk to a minimal case before being reported.
+
+use std::fmt::Debug;
+
+use anyhow::bail;
+
+use crate::{panics, rng::Rng};
+
+/// How many smaller candidates to try before settling for the smallest failing input found.
+const SHRINK_BUDGET: usize = 2000;
+
+/// Inputs that can be made smaller, for reporting the simplest case that still fails.
+pub trait Shrink: Sized {
+    /// Smaller versions of `self`, most aggressive first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+/// Removes ever smaller runs of `items`, from half of them down to single ones.
+fn without_chunks<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+    let mut size = items.len() / 2;
+    while size > 0 {
+        for start in (0..items.len()).step_by(size) {
+            let mut candidate = items[..start].to_vec();
+            candidate.extend_from_slice(&items[(start + size).min(items.len())..]);
+            candidates.push(candidate);
+        }
+        size /= 2;
+    }
+    if items.len() == 1 {
+        candidates.push(Vec::new());
+    }
+    candidates
+}
+
+/// Drops blank-line separated blocks first, then lines, so puzzle inputs keep their shape for as
+/// long as possible, and finally characters.
+impl Shrink for String {
+    fn shrink(&self) -> Vec<Self> {
+        let trailing_newline = if self.ends_with('\n') { "\n" } else { "" };
+        let blocks = self
+            .trim_end_matches('\n')
+            .split("\n\n")
+            .collect::<Vec<_>>();
+        let mut candidates = Vec::new();
+        if blocks.len() > 1 {
+            candidates.extend(
+                without_chunks(&blocks)
+                    .into_iter()
+                    .map(|blocks| blocks.join("\n\n") + trailing_newline),
+            );
+        }
+        let lines = self.lines().collect::<Vec<_>>();
+        candidates.extend(
+            without_chunks(&lines)
+                .into_iter()
+                .map(|lines| lines.join("\n") + trailing_newline),
+        );
+        if lines.len() <= 1 {
+            let chars = self.chars().collect::<Vec<_>>();
+            candidates.extend(
+                without_chunks(&chars)
+                    .into_iter()
+                    .map(|chars| chars.into_iter().collect()),
+            );
+        }
+        candidates
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = without_chunks(self);
+        for (i, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[i] = smaller;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+macro_rules! shrink_towards_zero {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let n = *self;
+                let mut candidates = Vec::new();
+                for candidate in [0, n / 2, n - n.signum()] {
+                    if candidate != n && !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+shrink_towards_zero!(i32, i64, isize);
+
+macro_rules! shrink_unsigned_towards_zero {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let n = *self;
+                let mut candidates = Vec::new();
+                for candidate in [0, n / 2, n.saturating_sub(1)] {
+                    if candidate != n && !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+shrink_unsigned_towards_zero!(u8, u32, u64, usize);
+
+type Variant<'a, I, O> = (String, Box<dyn Fn(&I) -> anyhow::Result<O> + 'a>);
+
+/// Runs every variant on generated inputs and fails with the smallest input they disagree on.
+/// Variants agree when they all return the same answer, or all fail.
+pub struct Differential<'a, I, O> {
+    generate: Box<dyn Fn(&mut Rng) -> I + 'a>,
+    variants: Vec<Variant<'a, I, O>>,
+    cases: usize,
+    seed: u64,
+}
+
+impl<'a, I, O> Differential<'a, I, O>
+where
+    I: Shrink + Debug,
+    O: PartialEq + Debug,
+{
+    pub fn new(generate: impl Fn(&mut Rng) -> I + 'a) -> Self {
+        Self {
+            generate: Box::new(generate),
+            variants: Vec::new(),
+            cases: 100,
+            seed: 0x5eed,
+        }
+    }
+
+    pub fn variant(mut self, name: &str, f: impl Fn(&I) -> anyhow::Result<O> + 'a) -> Self {
+        self.variants.push((name.to_string(), Box::new(f)));
+        self
+    }
+
+    /// How many inputs to generate, 100 by default.
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.variants.len() < 2 {
+            bail!("Need at least two variants to compare");
+        }
+        let mut rng = Rng::new(self.seed);
+        for case in 0..self.cases {
+            let input = (self.generate)(&mut rng);
+            if self.agree(&input) {
+                continue;
+            }
+            let input = self.shrink(input);
+            let outcomes = self
+                .outcomes(&input)
+                .into_iter()
+                .zip(&self.variants)
+                .map(|(outcome, (name, _))| format!("  {name}: {outcome:?}"))
+                .collect::<Vec<_>>();
+            bail!(
+                "Variants disagree on case {case} of seed {:#x}. Shrunk input:\n{input:?}\n{}",
+                self.seed,
+                outcomes.join("\n")
+            );
+        }
+        Ok(())
+    }
+
+    /// Each variant's answer, or its error (including panics) as a string.
+    fn outcomes(&self, input: &I) -> Vec<Result<O, String>> {
+        self.variants
+            .iter()
+            .map(|(name, f)| panics::catch(name, || f(input)).map_err(|error| format!("{error:#}")))
+            .collect()
+    }
+
+    fn agree(&self, input: &I) -> bool {
+        let outcomes = self.outcomes(input);
+        outcomes.iter().all(|outcome| outcome.is_err())
+            || outcomes
+                .windows(2)
+                .all(|pair| matches!(pair, [Ok(a), Ok(b)] if a == b))
+    }
+
+    fn shrink(&self, mut input: I) -> I {
+        let mut attempts = 0;
+        'smaller: while attempts < SHRINK_BUDGET {
+            for candidate in input.shrink() {
+                attempts += 1;
+                if !self.agree(&candidate) {
+                    input = candidate;
+                    continue 'smaller;
+                }
+                if attempts >= SHRINK_BUDGET {
+                    break;
+                }
+            }
+            break;
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(rng: &mut Rng) -> String {
+        let count = rng.below(20) + 1;
+        (0..count)
+            .map(|_| format!("{}\n", rng.range(0..1000)))
+            .collect()
+    }
+
+    fn sum(input: &str) -> anyhow::Result<i64> {
+        Ok(input.lines().map(|line| line.parse::<i64>().unwrap()).sum())
+    }
+
+    #[test]
+    fn agreeing_variants_pass() {
+        Differential::new(numbers)
+            .variant("sum", |input: &String| sum(input))
+            .variant("fold", |input: &String| {
+                input
+                    .lines()
+                    .try_fold(0, |total, line| Ok(total + line.parse::<i64>()?))
+            })
+            .check()
+            .unwrap();
+    }
+
+    #[test]
+    fn disagreements_are_shrunk() {
+        let error = Differential::new(numbers)
+            .variant("sum", |input: &String| sum(input))
+            .variant("sum ignoring 7s", |input: &String| {
+                Ok(input
+                    .lines()
+                    .map(|line| line.parse::<i64>().unwrap())
+                    .filter(|n| n % 10 != 7)
+                    .sum())
+            })
+            .check()
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Shrunk input:\n\"7"), "{error}");
+    }
+}
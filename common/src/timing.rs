@@ -0,0 +1,107 @@
+use std::time::{Duration, Instant};
+
+use crate::memory::{self, Usage};
+
+/// Collects how long each stage of a run takes, for `--time` and `--repeat`.
+#[derive(Debug)]
+pub struct Timer {
+    repeat: usize,
+    recording: bool,
+    samples: Vec<(String, Vec<Duration>)>,
+    /// Heap usage of each stage's last recorded run, when built with `alloc-stats`.
+    usage: Vec<(String, Usage)>,
+}
+
+impl Timer {
+    pub fn new(repeat: usize) -> Self {
+        Self {
+            repeat: repeat.max(1),
+            recording: true,
+            samples: Vec::new(),
+            usage: Vec::new(),
+        }
+    }
+
+    /// Runs `f` and records how long it took, and what it allocated, under `label`, unless this is
+    /// a warm-up run.
+    pub fn time<T>(&mut self, label: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let (result, usage) = memory::measure(f);
+        let elapsed = start.elapsed();
+        if self.recording {
+            match self.samples.iter_mut().find(|(l, _)| l == label) {
+                Some((_, samples)) => samples.push(elapsed),
+                None => self.samples.push((label.to_string(), vec![elapsed])),
+            }
+            if let Some(usage) = usage {
+                self.usage.retain(|(l, _)| l != label);
+                self.usage.push((label.to_string(), usage));
+            }
+        }
+        result
+    }
+
+    /// Runs `f` the requested number of times, after an unrecorded warm-up run if there's more
+    /// than one, and returns the last result.
+    pub fn repeat<T>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        if self.repeat > 1 {
+            self.recording = false;
+            f(self)?;
+            self.recording = true;
+        }
+        let mut result = f(self)?;
+        for _ in 1..self.repeat {
+            result = f(self)?;
+        }
+        Ok(result)
+    }
+
+    /// One line per stage with its median time, and the spread when it ran more than once, followed
+    /// by its heap usage when that's being counted.
+    pub fn report(&self) -> String {
+        let width = self.samples.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
+        self.samples
+            .iter()
+            .map(|(label, samples)| {
+                let mut sorted = samples.clone();
+                sorted.sort();
+                let median = sorted[sorted.len() / 2];
+                let mut line = if sorted.len() == 1 {
+                    format!("{label:<width$}  {median:?}")
+                } else {
+                    format!(
+                        "{label:<width$}  {median:?} (median of {}, min {:?}, max {:?})",
+                        sorted.len(),
+                        sorted[0],
+                        sorted[sorted.len() - 1],
+                    )
+                };
+                if let Some((_, usage)) = self.usage.iter().find(|(l, _)| l == label) {
+                    line.push_str(&format!(", {usage}"));
+                }
+                line + "\n"
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_skips_the_warm_up_run() {
+        let mut timer = Timer::new(3);
+        let mut runs = 0;
+        let result = timer.repeat(|timer| {
+            runs += 1;
+            Ok(timer.time("part 1 solve", || runs))
+        });
+        assert_eq!(result.unwrap(), 4);
+        assert_eq!(timer.samples[0].1.len(), 3);
+        assert!(timer.report().contains("(median of 3,"));
+    }
+}
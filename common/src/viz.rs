@@ -0,0 +1,345 @@
+//! Records snapshots of a grid as a simulation runs, playing each back in the terminal or saving
+//! it as an image as soon as it's recorded. Recording only happens with `--viz`, so days can leave
+//! their [`record`] calls in place.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
+
+use crate::{
+    cli::{VizArgs, VizOutput},
+    grid::{coordinates::Point, Grid, Render},
+};
+
+/// Width and height in pixels of one grid cell in saved images.
+const CELL_PIXELS: usize = 4;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+impl Colour {
+    pub const BLACK: Colour = Colour(0, 0, 0);
+    pub const GREY: Colour = Colour(96, 96, 96);
+    pub const WHITE: Colour = Colour(255, 255, 255);
+    pub const RED: Colour = Colour(220, 50, 47);
+    pub const GREEN: Colour = Colour(133, 153, 0);
+    pub const BLUE: Colour = Colour(38, 139, 210);
+    pub const YELLOW: Colour = Colour(181, 137, 0);
+}
+
+/// How one grid cell is drawn: `glyph` in the terminal, `colour` in both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Colour,
+}
+
+impl Cell {
+    pub fn new(glyph: char, colour: Colour) -> Self {
+        Self { glyph, colour }
+    }
+}
+
+/// Empty-looking glyphs are dark and everything else is white.
+impl From<char> for Cell {
+    fn from(glyph: char) -> Self {
+        let colour = match glyph {
+            ' ' => Colour::BLACK,
+            '.' => Colour::GREY,
+            _ => Colour::WHITE,
+        };
+        Self { glyph, colour }
+    }
+}
+
+type Frame = Render;
+
+#[derive(Debug, Clone)]
+struct Settings {
+    output: VizOutput,
+    dir: std::path::PathBuf,
+    fps: u16,
+}
+
+/// Draws or writes each frame as it's recorded, so frames are never held in memory.
+struct Recorder {
+    settings: Settings,
+    frames: usize,
+    gif: Option<gif::Encoder<BufWriter<File>>>,
+    error: Option<anyhow::Error>,
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Turns recording on if `--viz` was given.
+pub(crate) fn configure(args: &VizArgs) {
+    let Some(output) = args.viz else {
+        return;
+    };
+    *RECORDER.lock().unwrap() = Some(Recorder {
+        settings: Settings {
+            output,
+            dir: args.viz_dir.clone(),
+            fps: args.viz_fps,
+        },
+        frames: 0,
+        gif: None,
+        error: None,
+    });
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether frames are being recorded, for days that need extra work to build a grid to record.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Records a snapshot of `grid`, drawing each cell with `cell`. Does nothing without `--viz`.
+pub fn record<T, C: Into<Cell>>(grid: &Grid<T>, cell: impl Fn(Point, &T) -> C) {
+    record_with(|| grid.render(cell));
+}
+
+/// Records the [`Render`] made by `render`, for frames with overlays. `render` is only called
+/// with `--viz`.
+pub fn record_with(render: impl FnOnce() -> Render) {
+    if !enabled() {
+        return;
+    }
+    let mut recorder = RECORDER.lock().unwrap();
+    let Some(recorder) = recorder.as_mut() else {
+        return;
+    };
+    if let Err(error) = recorder.write(&render()) {
+        // Stop recording and report the error once the part finishes.
+        ENABLED.store(false, Ordering::Relaxed);
+        recorder.error = Some(error);
+    }
+}
+
+impl Recorder {
+    fn write(&mut self, frame: &Frame) -> anyhow::Result<()> {
+        let Settings { output, dir, fps } = &self.settings;
+        if self.frames == 0 && *output != VizOutput::Terminal {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Couldn't create {}", dir.display()))?;
+        }
+        match output {
+            VizOutput::Terminal => draw(frame, self.frames == 0, *fps)?,
+            VizOutput::Ppm => write_frame(dir, self.frames, "ppm", |file| {
+                Ok(file.write_all(&ppm(frame))?)
+            })?,
+            VizOutput::Png => write_frame(dir, self.frames, "png", |file| write_png(file, frame))?,
+            VizOutput::Gif => {
+                if self.gif.is_none() {
+                    self.gif = Some(gif_encoder(&dir.join("animation.gif"), frame)?);
+                }
+                let encoder = self.gif.as_mut().unwrap();
+                encoder.write_frame(&gif_frame(frame, *fps)?)?;
+            }
+        }
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+/// Finishes off whatever was recorded, as chosen with `--viz`.
+pub(crate) fn finish() -> anyhow::Result<()> {
+    let Some(recorder) = RECORDER.lock().unwrap().take() else {
+        return Ok(());
+    };
+    ENABLED.store(false, Ordering::Relaxed);
+    if let Some(error) = recorder.error {
+        return Err(error.context("Couldn't record the visualisation"));
+    }
+    if recorder.frames == 0 {
+        tracing::warn!("--viz was given, but no frames were recorded");
+        return Ok(());
+    }
+    let Settings { output, dir, .. } = recorder.settings;
+    if let Some(encoder) = recorder.gif {
+        encoder.into_inner()?.flush()?;
+        let path = dir.join("animation.gif");
+        tracing::info!("Wrote {} frames to {}", recorder.frames, path.display());
+    } else if output != VizOutput::Terminal {
+        tracing::info!("Wrote {} frames to {}", recorder.frames, dir.display());
+    }
+    Ok(())
+}
+
+/// Draws a frame over the last on stderr, keeping stdout for the answer, then waits for the next.
+fn draw(frame: &Frame, first: bool, fps: u16) -> anyhow::Result<()> {
+    let mut stderr = std::io::stderr().lock();
+    if first {
+        write!(stderr, "\x1b[2J")?;
+    }
+    let out = String::from("\x1b[H") + &frame.coloured();
+    stderr.write_all(out.as_bytes())?;
+    stderr.flush()?;
+    std::thread::sleep(Duration::from_secs(1) / fps.into());
+    Ok(())
+}
+
+/// A row of cells in their colours, resetting the colour at the end.
+pub(crate) fn ansi_line(cells: &[Cell]) -> String {
+    let mut line = String::new();
+    for cell in cells {
+        let Colour(r, g, b) = cell.colour;
+        line.push_str(&format!("\x1b[38;2;{r};{g};{b}m{}", cell.glyph));
+    }
+    line + "\x1b[0m"
+}
+
+fn write_frame(
+    dir: &Path,
+    index: usize,
+    extension: &str,
+    write: impl FnOnce(&mut BufWriter<File>) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let path = dir.join(format!("frame_{index:05}.{extension}"));
+    let mut file = BufWriter::new(
+        File::create(&path).with_context(|| format!("Couldn't create {}", path.display()))?,
+    );
+    write(&mut file)?;
+    file.flush()?;
+    Ok(())
+}
+
+/// The frame as RGB bytes, with each cell blown up to a square of [`CELL_PIXELS`].
+fn rgb(frame: &Frame) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(frame.cells.len() * CELL_PIXELS * CELL_PIXELS * 3);
+    for row in frame.rows() {
+        for _ in 0..CELL_PIXELS {
+            for cell in row {
+                let Colour(r, g, b) = cell.colour;
+                for _ in 0..CELL_PIXELS {
+                    pixels.extend([r, g, b]);
+                }
+            }
+        }
+    }
+    pixels
+}
+
+fn ppm(frame: &Frame) -> Vec<u8> {
+    let mut image = format!(
+        "P6\n{} {}\n255\n",
+        frame.width * CELL_PIXELS,
+        frame.height * CELL_PIXELS
+    )
+    .into_bytes();
+    image.extend(rgb(frame));
+    image
+}
+
+fn write_png(file: &mut BufWriter<File>, frame: &Frame) -> anyhow::Result<()> {
+    let mut encoder = png::Encoder::new(
+        file,
+        (frame.width * CELL_PIXELS) as u32,
+        (frame.height * CELL_PIXELS) as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&rgb(frame))?;
+    Ok(())
+}
+
+/// A looping GIF the size of `first`. Each frame brings its own palette.
+fn gif_encoder(path: &Path, first: &Frame) -> anyhow::Result<gif::Encoder<BufWriter<File>>> {
+    let file = File::create(path).with_context(|| format!("Couldn't create {}", path.display()))?;
+    let mut encoder = gif::Encoder::new(
+        BufWriter::new(file),
+        (first.width * CELL_PIXELS).try_into()?,
+        (first.height * CELL_PIXELS).try_into()?,
+        &[],
+    )?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    Ok(encoder)
+}
+
+/// One GIF frame, whose palette has room for 256 distinct colours.
+fn gif_frame(frame: &Frame, fps: u16) -> anyhow::Result<gif::Frame<'static>> {
+    let mut palette = HashMap::new();
+    for cell in &frame.cells {
+        let next = palette.len();
+        palette.entry(cell.colour).or_insert(next);
+    }
+    if palette.len() > 256 {
+        bail!(
+            "A GIF frame can only have 256 colours but this one uses {}",
+            palette.len()
+        );
+    }
+    let mut local_palette = vec![0; palette.len() * 3];
+    for (&Colour(r, g, b), &i) in &palette {
+        local_palette[i * 3..i * 3 + 3].copy_from_slice(&[r, g, b]);
+    }
+    let indices = rgb(frame)
+        .chunks(3)
+        .map(|rgb| palette[&Colour(rgb[0], rgb[1], rgb[2])] as u8)
+        .collect::<Vec<_>>();
+    Ok(gif::Frame {
+        width: (frame.width * CELL_PIXELS).try_into()?,
+        height: (frame.height * CELL_PIXELS).try_into()?,
+        buffer: Cow::Owned(indices),
+        palette: Some(local_palette),
+        delay: 100 / fps,
+        ..gif::Frame::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_scales_cells_into_pixels() {
+        let frame = Frame {
+            width: 2,
+            height: 1,
+            cells: vec![Cell::from('#'), Cell::new('S', Colour::RED)],
+        };
+        let image = ppm(&frame);
+        let header = format!("P6\n{} {}\n255\n", 2 * CELL_PIXELS, CELL_PIXELS);
+        assert!(image.starts_with(header.as_bytes()));
+        let pixels = &image[header.len()..];
+        assert_eq!(pixels.len(), 2 * CELL_PIXELS * CELL_PIXELS * 3);
+        assert_eq!(&pixels[..3], &[255, 255, 255]);
+        assert_eq!(
+            &pixels[CELL_PIXELS * 3..CELL_PIXELS * 3 + 3],
+            &[220, 50, 47]
+        );
+
+        let empty = Frame {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        };
+        assert!(rgb(&empty).is_empty());
+    }
+
+    #[test]
+    fn it_gives_each_gif_frame_its_own_palette() {
+        let frame = Frame {
+            width: 3,
+            height: 1,
+            cells: vec![Cell::from('#'), Cell::from('#'), Cell::from(' ')],
+        };
+        let gif = gif_frame(&frame, 10).unwrap();
+        assert_eq!(gif.palette.as_ref().unwrap().len(), 2 * 3);
+        assert_eq!(gif.buffer.len(), 3 * CELL_PIXELS * CELL_PIXELS);
+        assert_eq!(gif.delay, 10);
+    }
+}